@@ -324,9 +324,94 @@ export async function registerMemoryOnChain(
   }
 }
 
+/**
+ * Build a field -> byte offset map from Borsh fields listed in order.
+ */
+function layoutOffsets<T extends string>(
+  fields: ReadonlyArray<readonly [T, number]>,
+): Record<T, number> & { size: number } {
+  const offsets = {} as Record<T, number>;
+  let size = 0;
+  for (const [name, bytes] of fields) {
+    offsets[name] = size;
+    size += bytes;
+  }
+  return Object.assign(offsets, { size });
+}
+
+/**
+ * Raw layout of the MemoryRegistry account, mirroring `MemoryRegistry` and
+ * `MemoryEntry` in programs/memory-registry/src/state.rs (see `BASE_SIZE`
+ * and `ENTRY_SIZE` there). Update both together whenever the layout changes
+ * and bump `schemaVersion` with the program's `SCHEMA_VERSION`. The header
+ * ends with the entries length prefix, so `header.size` is where entry 0
+ * starts; entries are packed `entry.size` bytes apart.
+ */
+export const REGISTRY_LAYOUT = {
+  header: layoutOffsets([
+    ['discriminator', 8],
+    ['authority', 32],
+    ['memoryCount', 8],
+    ['bump', 1],
+    ['schemaVersion', 4],
+    ['typeCounts', 16],
+    ['typeCaps', 16],
+    ['tierCounts', 12],
+    ['duplicatePolicy', 1],
+    ['tierTtls', 24],
+    ['schemas', 32],
+    ['schemaCount', 1],
+    ['nonce', 8],
+    ['currentEpoch', 4],
+    ['expiresAt', 8],
+    ['coAuthority', 32],
+    ['writeGrants', 128],
+    ['ringCapacity', 4],
+    ['decayAfterSeconds', 8],
+    ['encryptedCount', 4],
+    ['maxRetained', 4],
+    ['requireEncryption', 1],
+    ['timeOracle', 32],
+    ['burstThreshold', 4],
+    ['burstWindow', 8],
+    ['burstWindowStart', 8],
+    ['burstCount', 4],
+    ['frozen', 1],
+    ['entriesLen', 4],
+  ] as const),
+  entry: layoutOffsets([
+    ['contentHash', 32],
+    ['timestamp', 8],
+    ['memoryType', 1],
+    ['importanceTier', 1],
+    ['memoryId', 8],
+    ['flags', 2],
+    ['revision', 2],
+    ['expiresAt', 8],
+    ['schemaId', 2],
+    ['previewHash', 32],
+    ['epoch', 4],
+    ['confidence', 1],
+    ['decaySteps', 1],
+    ['encryptionNonce', 12],
+    ['appData', 32],
+    ['contentKind', 1],
+    ['clusterId', 2],
+    ['lastModified', 8],
+    ['derivedFrom', 32],
+    ['derivedCount', 1],
+  ] as const),
+  /** `VERSION_TAG | SCHEMA_VERSION`: the only layout this client parses. */
+  schemaVersion: 0x8000_0001,
+  /** `MemoryEntry::HIDDEN_FLAGS`: FLAG_PENDING | FLAG_QUARANTINED. */
+  HIDDEN_FLAGS: (1 << 1) | (1 << 4),
+} as const;
+
 /**
  * Verify a content hash exists in the on-chain registry.
- * Returns true if found, false otherwise.
+ * Returns true if found, false otherwise. Matches the program's
+ * `verify_memory`: an expired registry, the all-zero hash, pending
+ * reservations and quarantined entries all verify as false.
  */
 export async function verifyMemoryOnChain(
   contentHash: Buffer,
@@ -335,6 +420,8 @@ export async function verifyMemoryOnChain(
   const wallet = getBotWallet();
   const authPubkey = authority || wallet?.publicKey;
   if (!authPubkey || !registryProgramId) return false;
+  // The zero hash is what pending reservations carry; it is never a memory.
+  if (contentHash.length !== 32 || contentHash.every((b) => b === 0)) return false;
 
   const conn = getConnection();
   const [registryPDA] = deriveRegistryPDA(authPubkey);
@@ -343,22 +430,31 @@ export async function verifyMemoryOnChain(
     const accountInfo = await conn.getAccountInfo(registryPDA);
     if (!accountInfo || !accountInfo.data) return false;
 
-    // Parse the account data (Anchor discriminator + Borsh)
-    // Skip 8 bytes discriminator + 32 bytes authority + 8 bytes memory_count + 1 byte bump
-    // Then 4 bytes vec length prefix, then entries
+    // Parse the account data (Anchor discriminator + Borsh) at the offsets
+    // REGISTRY_LAYOUT derives from the field list.
+    const { header, entry } = REGISTRY_LAYOUT;
     const data = accountInfo.data;
-    if (data.length < 53) return false; // Too small to contain any entries
+    if (data.length < header.size) return false; // Too small to contain any entries
+    if (data.readUInt32LE(header.schemaVersion) !== REGISTRY_LAYOUT.schemaVersion) {
+      log.warn({ registry: registryPDA.toBase58() }, 'Unsupported registry layout version');
+      return false;
+    }
 
-    const vecLen = data.readUInt32LE(49);
-    const ENTRY_SIZE = 56; // Matches MemoryEntry on-chain size
-    const entriesStart = 53;
+    const expiresAt = data.readBigInt64LE(header.expiresAt);
+    const now = BigInt(Math.floor(Date.now() / 1000));
+    if (expiresAt !== 0n && now >= expiresAt) return false;
 
+    const vecLen = data.readUInt32LE(header.entriesLen);
     for (let i = 0; i < vecLen; i++) {
-      const offset = entriesStart + i * ENTRY_SIZE;
-      if (offset + 32 > data.length) break;
+      const offset = header.size + i * entry.size;
+      if (offset + entry.size > data.length) break;
 
       const hash = data.subarray(offset, offset + 32);
-      if (contentHash.equals(hash)) return true;
+      if (!contentHash.equals(hash)) continue;
+      // Pending and quarantined entries are hidden from verification, as
+      // on-chain.
+      const flags = data.readUInt16LE(offset + entry.flags);
+      if ((flags & REGISTRY_LAYOUT.HIDDEN_FLAGS) === 0) return true;
     }
 
     return false;
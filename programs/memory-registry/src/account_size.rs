@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::errors::RegistryError;
+use crate::state::MemoryRegistry;
+
+/// Shrink a registry account to `space_for(slots)` and refund the rent that
+/// is no longer needed to `recipient`. No-op (returns 0) when the account is
+/// already at or below that size. Returns lamports refunded.
+///
+/// The registry is program-owned, so lamports can be debited directly. The
+/// target size must still fit the serialized registry — Anchor writes the
+/// account back on exit.
+pub fn shrink_registry<'info>(
+    registry: &Account<'info, MemoryRegistry>,
+    recipient: &AccountInfo<'info>,
+    slots: usize,
+) -> Result<u64> {
+    let info = registry.to_account_info();
+    let rent = Rent::get()?;
+    let Some((new_len, refund)) = shrink_plan(&rent, info.data_len(), info.lamports(), slots) else {
+        return Ok(0);
+    };
+
+    info.resize(new_len)?;
+
+    if refund > 0 {
+        **info.try_borrow_mut_lamports()? -= refund;
+        **recipient.try_borrow_mut_lamports()? += refund;
+    }
+
+    Ok(refund)
+}
+
+/// The new length and refund `shrink_registry` applies to an account of
+/// `data_len` bytes holding `lamports`, or `None` when it is already at or
+/// below `space_for(slots)`.
+pub fn shrink_plan(
+    rent: &Rent,
+    data_len: usize,
+    lamports: u64,
+    slots: usize,
+) -> Option<(usize, u64)> {
+    let new_len = MemoryRegistry::space_for(slots);
+    if data_len <= new_len {
+        return None;
+    }
+    Some((new_len, lamports.saturating_sub(rent.minimum_balance(new_len))))
+}
+
+/// Fail with `NotRentExempt` unless `info` holds the rent-exempt minimum for
+/// its current length. The growing handlers run this after Anchor's realloc
+/// has topped the account up, so a shortfall (e.g. from rent rounding)
+/// fails the write instead of leaving a rent-collectible account.
+pub fn require_rent_exempt(info: &AccountInfo) -> Result<()> {
+    check_rent_exempt(&Rent::get()?, info.lamports(), info.data_len())
+}
+
+/// `require_rent_exempt` against explicit `rent` parameters.
+pub fn check_rent_exempt(rent: &Rent, lamports: u64, data_len: usize) -> Result<()> {
+    require!(
+        lamports >= rent.minimum_balance(data_len),
+        RegistryError::NotRentExempt
+    );
+    Ok(())
+}
+
+/// Grow `info` towards `target_len`, by at most the runtime's 10 KiB
+/// per-instruction limit, with `payer` topping up rent through
+/// `system_program`. Returns the new length; call again until it reaches
+/// `target_len`. Works on raw bytes, so the account need not deserialize.
+pub fn grow_account<'info>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    target_len: usize,
+) -> Result<usize> {
+    let len = info.data_len();
+    if len >= target_len {
+        return Ok(len);
+    }
+    let new_len = target_len.min(len + MAX_PERMITTED_DATA_INCREASE);
+
+    let top_up = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(info.lamports());
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    info.resize(new_len)?;
+    Ok(new_len)
+}
+
+/// Fail with `RegistryTooSmall` unless `info` already has room for a
+/// registry of `slots` entries. Handlers that can need more than one
+/// instruction's worth of growth check this instead of reallocating; the
+/// client grows the account first with `grow_registry`.
+pub fn require_capacity(info: &AccountInfo, slots: usize) -> Result<()> {
+    require!(
+        info.data_len() >= MemoryRegistry::space_for(slots),
+        RegistryError::RegistryTooSmall
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rent_exemption_holds_exactly_at_the_minimum() {
+        let rent = Rent::default();
+        let len = MemoryRegistry::space_for(3);
+        let minimum = rent.minimum_balance(len);
+        assert!(check_rent_exempt(&rent, minimum, len).is_ok());
+        assert!(check_rent_exempt(&rent, minimum - 1, len).is_err());
+        // One more byte of data needs more lamports than were enough before.
+        assert!(check_rent_exempt(&rent, minimum, len + 1).is_err());
+    }
+
+    #[test]
+    fn rounding_down_the_minimum_is_not_a_shortfall() {
+        // A fractional threshold makes the exact minimum non-integral:
+        // (128 + 1) * 3 * 1.5 = 580.5, which the runtime truncates to 580.
+        let rent = Rent {
+            lamports_per_byte_year: 3,
+            exemption_threshold: 1.5,
+            ..Rent::default()
+        };
+        assert_eq!(rent.minimum_balance(1), 580);
+        assert!(check_rent_exempt(&rent, 580, 1).is_ok());
+        assert!(check_rent_exempt(&rent, 579, 1).is_err());
+    }
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+use crate::errors::RegistryError;
+
+/// Current unix timestamp from the Clock sysvar. `Clock::get` only fails
+/// where the sysvar isn't available (some CPI contexts and test harnesses);
+/// that surfaces as `ClockUnavailable` instead of a generic syscall error.
+///
+/// There is deliberately no caller-supplied fallback: entry timestamps back
+/// `verify_before` proofs, so letting a caller pick one would allow
+/// backdating. The only other source is `register_memory_timestamped`, whose
+/// timestamps must be signed by the authority-chosen time oracle. Bulk
+/// replaces and restores carry entries with timestamps attached, but
+/// `replace_entries` ignores them: it keeps the stored timestamp of a hash
+/// already registered and stamps the rest with this clock.
+pub fn unix_now() -> Result<i64> {
+    Clock::get()
+        .map(|clock| clock.unix_timestamp)
+        .map_err(|_| error!(RegistryError::ClockUnavailable))
+}
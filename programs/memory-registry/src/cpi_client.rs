@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::pda::registry_pda;
+
+/// Whether `content_hash` is registered in `authority`'s registry, for
+/// programs consuming the registry over CPI. `registry` must be that
+/// authority's registry PDA (see `registry_pda`).
+///
+/// A failing CPI aborts the whole transaction, so a caller can't branch on
+/// `verify_memory`'s `HashNotFound`. This calls `lookup` instead, whose miss
+/// is `Ok(None)`. An expired registry still fails, with `RegistryExpired`,
+/// exactly as `verify_memory` does.
+pub fn verify_memory_cpi<'info>(
+    registry_program: AccountInfo<'info>,
+    registry: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    content_hash: [u8; 32],
+) -> Result<bool> {
+    let ctx = CpiContext::new(
+        registry_program,
+        crate::cpi::accounts::ReadRegistry {
+            registry,
+            authority,
+        },
+    );
+    let entry = crate::cpi::lookup(ctx, content_hash)?.get();
+    Ok(entry.is_some())
+}
+
+/// Account metas for a `lookup` / `verify_memory_cpi` call against
+/// `authority`'s registry, for callers assembling instructions by hand.
+pub fn lookup_account_metas(authority: Pubkey) -> Vec<AccountMeta> {
+    crate::accounts::ReadRegistry {
+        registry: registry_pda(&authority).0,
+        authority,
+    }
+    .to_account_metas(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::{ReadRegistry, ReadRegistryBumps};
+    use crate::state::MemoryRegistry;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn lookup_account_metas_satisfy_read_registry() {
+        let authority = Pubkey::new_unique();
+        let metas = lookup_account_metas(authority);
+        assert_eq!(metas.len(), 2);
+        assert!(metas.iter().all(|meta| !meta.is_writable && !meta.is_signer));
+
+        let (address, bump) = registry_pda(&authority);
+        assert_eq!((metas[0].pubkey, metas[1].pubkey), (address, authority));
+        let mut data = Vec::new();
+        MemoryRegistry::new(authority, bump, 0)
+            .try_serialize(&mut data)
+            .unwrap();
+        let (mut registry_lamports, mut authority_lamports) = (1, 1);
+        let (owner, system) = (crate::ID, Pubkey::default());
+        let infos = [
+            AccountInfo::new(
+                &metas[0].pubkey, false, false, &mut registry_lamports, &mut data, &owner, false, 0,
+            ),
+            AccountInfo::new(
+                &metas[1].pubkey, false, false, &mut authority_lamports, &mut [], &system, false, 0,
+            ),
+        ];
+        let accounts = ReadRegistry::try_accounts(
+            &crate::ID,
+            &mut &infos[..],
+            &[],
+            &mut ReadRegistryBumps {},
+            &mut BTreeSet::new(),
+        );
+        assert!(accounts.is_ok());
+    }
+}
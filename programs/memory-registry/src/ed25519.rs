@@ -0,0 +1,183 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::get_instruction_relative;
+
+/// Layout of the Ed25519 precompile instruction data: a 2-byte header
+/// (`num_signatures: u8`, padding) followed by one 14-byte offsets record per
+/// signature, then the inline signature / pubkey / message bytes.
+const HEADER_LEN: usize = 2;
+const OFFSETS_LEN: usize = 14;
+const SIGNATURE_LEN: usize = 64;
+const PUBKEY_LEN: usize = 32;
+/// Instruction-index value meaning "data lives in this same instruction".
+const SAME_INSTRUCTION: u16 = u16::MAX;
+
+/// Check that the instruction immediately before the current one is an
+/// Ed25519 precompile verification of exactly (`pubkey`, `message`,
+/// `signature`). The runtime rejects the whole transaction if the precompile
+/// signature is bad, so finding a matching instruction proves the signature.
+///
+/// `instructions_sysvar` must be the Instructions sysvar account.
+pub fn verify_preceding_ed25519(
+    instructions_sysvar: &AccountInfo,
+    pubkey: &Pubkey,
+    message: &[u8],
+    signature: &[u8; 64],
+) -> bool {
+    let Ok(ix) = get_instruction_relative(-1, instructions_sysvar) else {
+        return false;
+    };
+    ix.program_id == ed25519_program::ID
+        && precompile_data_matches(&ix.data, pubkey, message, signature)
+}
+
+/// Whether Ed25519 precompile instruction `data` verifies exactly one
+/// (`pubkey`, `message`, `signature`), all inlined in `data` itself.
+fn precompile_data_matches(
+    data: &[u8],
+    pubkey: &Pubkey,
+    message: &[u8],
+    signature: &[u8; 64],
+) -> bool {
+    if data.len() < HEADER_LEN + OFFSETS_LEN || data[0] != 1 {
+        return false;
+    }
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let offsets = HEADER_LEN;
+    let signature_offset = read_u16(offsets) as usize;
+    let signature_ix = read_u16(offsets + 2);
+    let pubkey_offset = read_u16(offsets + 4) as usize;
+    let pubkey_ix = read_u16(offsets + 6);
+    let message_offset = read_u16(offsets + 8) as usize;
+    let message_len = read_u16(offsets + 10) as usize;
+    let message_ix = read_u16(offsets + 12);
+
+    // Only accept data inlined in the precompile instruction itself, so the
+    // bytes compared below are the bytes the runtime actually verified.
+    if signature_ix != SAME_INSTRUCTION
+        || pubkey_ix != SAME_INSTRUCTION
+        || message_ix != SAME_INSTRUCTION
+    {
+        return false;
+    }
+
+    let slice = |at: usize, len: usize| data.get(at..at.checked_add(len)?);
+    slice(signature_offset, SIGNATURE_LEN) == Some(&signature[..])
+        && slice(pubkey_offset, PUBKEY_LEN) == Some(pubkey.as_ref())
+        && slice(message_offset, message_len) == Some(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::sysvar::instructions::{
+        self as sysvar_instructions, construct_instructions_data, BorrowedInstruction,
+    };
+
+    /// Precompile data laid out as the Solana SDK builds it: offsets, then
+    /// pubkey, signature and message inline.
+    fn precompile_data(pubkey: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Vec<u8> {
+        let pubkey_offset = HEADER_LEN + OFFSETS_LEN;
+        let signature_offset = pubkey_offset + PUBKEY_LEN;
+        let message_offset = signature_offset + SIGNATURE_LEN;
+        let mut data = vec![1, 0];
+        for value in [
+            signature_offset as u16,
+            SAME_INSTRUCTION,
+            pubkey_offset as u16,
+            SAME_INSTRUCTION,
+            message_offset as u16,
+            message.len() as u16,
+            SAME_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(pubkey.as_ref());
+        data.extend_from_slice(signature);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn accepts_the_exact_signed_triple() {
+        let (pubkey, signature) = (Pubkey::new_unique(), [7; 64]);
+        let data = precompile_data(&pubkey, &signature, b"message");
+        assert!(precompile_data_matches(&data, &pubkey, b"message", &signature));
+
+        assert!(!precompile_data_matches(&data, &Pubkey::new_unique(), b"message", &signature));
+        assert!(!precompile_data_matches(&data, &pubkey, b"messagf", &signature));
+        assert!(!precompile_data_matches(&data, &pubkey, b"message", &[8; 64]));
+    }
+
+    /// Instructions sysvar data for `instructions`, with the one at `current`
+    /// executing.
+    fn sysvar_data(instructions: &[(Pubkey, Vec<u8>)], current: u16) -> Vec<u8> {
+        let borrowed: Vec<BorrowedInstruction> = instructions
+            .iter()
+            .map(|(program_id, data)| BorrowedInstruction {
+                program_id,
+                accounts: Vec::new(),
+                data,
+            })
+            .collect();
+        // The runtime stores the current index in the trailing two bytes.
+        let mut data = construct_instructions_data(&borrowed);
+        let at = data.len() - 2;
+        data[at..].copy_from_slice(&current.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn verifies_through_the_instructions_sysvar() {
+        let (oracle, signature) = (Pubkey::new_unique(), [7; 64]);
+        let precompile = precompile_data(&oracle, &signature, b"message");
+        let registry_ix = (crate::ID, vec![0; 8]);
+        let check = |data: &mut Vec<u8>, pubkey: &Pubkey, signature: &[u8; 64]| {
+            let mut lamports = 0;
+            let (key, owner) = (sysvar_instructions::ID, Pubkey::default());
+            let info = AccountInfo::new(&key, false, false, &mut lamports, data, &owner, false, 0);
+            verify_preceding_ed25519(&info, pubkey, b"message", signature)
+        };
+
+        let verified = (ed25519_program::ID, precompile.clone());
+        let mut valid = sysvar_data(&[verified, registry_ix.clone()], 1);
+        assert!(check(&mut valid, &oracle, &signature));
+
+        // Forged: a signature or key the precompile never verified.
+        assert!(!check(&mut valid, &oracle, &[8; 64]));
+        assert!(!check(&mut valid, &Pubkey::new_unique(), &signature));
+
+        // Forged: the same bytes, but not run through the precompile.
+        let lookalike = (Pubkey::new_unique(), precompile.clone());
+        let mut unverified = sysvar_data(&[lookalike, registry_ix.clone()], 1);
+        assert!(!check(&mut unverified, &oracle, &signature));
+
+        // The precompile must come immediately before the current instruction.
+        let mut first = sysvar_data(&[registry_ix, (ed25519_program::ID, precompile)], 0);
+        assert!(!check(&mut first, &oracle, &signature));
+    }
+
+    #[test]
+    fn rejects_malformed_or_indirect_data() {
+        let (pubkey, signature) = (Pubkey::new_unique(), [7; 64]);
+        let data = precompile_data(&pubkey, &signature, b"message");
+        let check = |data: &[u8]| precompile_data_matches(data, &pubkey, b"message", &signature);
+
+        assert!(!check(&data[..HEADER_LEN + OFFSETS_LEN - 1]));
+        assert!(!check(&data[..data.len() - 1]), "message runs past the end");
+
+        let mut two_signatures = data.clone();
+        two_signatures[0] = 2;
+        assert!(!check(&two_signatures));
+
+        // Data pulled from another instruction isn't what was compared here.
+        let mut indirect = data.clone();
+        indirect[HEADER_LEN + 2..HEADER_LEN + 4].copy_from_slice(&0u16.to_le_bytes());
+        assert!(!check(&indirect));
+
+        let mut overflowing = data;
+        overflowing[HEADER_LEN..HEADER_LEN + 2].copy_from_slice(&u16::MAX.to_le_bytes());
+        assert!(!check(&overflowing));
+    }
+}
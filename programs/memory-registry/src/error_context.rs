@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use crate::errors::RegistryError;
+
+/// Machine-readable detail for a failed instruction. Anchor errors carry only
+/// a code and a static message, so selected failures also leave this in
+/// return data (visible to clients that simulate) and in the logs.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ErrorContext {
+    /// Anchor error code (`ERROR_CODE_OFFSET` + variant index).
+    pub error_code: u32,
+    /// What the error is about, e.g. the memory type for `TypeCapExceeded`.
+    pub subject: u64,
+    /// Current value that hit the limit, e.g. live entries of that type.
+    pub observed: u64,
+    /// The limit itself, e.g. the configured cap.
+    pub limit: u64,
+}
+
+/// Record `ErrorContext` for `error` and return the error to propagate.
+pub fn fail_with_context(error: RegistryError, subject: u64, observed: u64, limit: u64) -> Error {
+    let context = ErrorContext {
+        error_code: error.into(),
+        subject,
+        observed,
+        limit,
+    };
+    msg!(
+        "Error context: code={} subject={} observed={} limit={}",
+        context.error_code,
+        context.subject,
+        context.observed,
+        context.limit
+    );
+    // Borsh serialization of a fixed-size struct into a Vec can't fail.
+    set_return_data(&context.try_to_vec().unwrap_or_default());
+    error.into()
+}
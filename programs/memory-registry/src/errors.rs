@@ -28,4 +28,112 @@ pub enum RegistryError {
     InsufficientCitationFee,
     #[msg("Cannot cite your own memory (self-citation is disallowed)")]
     SelfCitation,
+
+    // --- Registry (appended after first deploy) ---
+    #[msg("Registry has no entries")]
+    RegistryEmpty,
+    #[msg("Memory type has reached its configured cap")]
+    TypeCapExceeded,
+    #[msg("Required parent memory is not in the registry")]
+    DependencyMissing,
+    #[msg("Ownership proof signature does not match the registry authority")]
+    InvalidOwnershipProof,
+    #[msg("Invalid duplicate policy — must be 0-2")]
+    InvalidDuplicatePolicy,
+    #[msg("Content hash must be non-zero (zero marks a pending reservation)")]
+    InvalidContentHash,
+    #[msg("A reservation for this memory_id already exists")]
+    ReservationExists,
+    #[msg("No pending reservation for this memory_id")]
+    ReservationNotFound,
+    #[msg("Invalid importance tier — must be 0-2")]
+    InvalidImportanceTier,
+    #[msg("Expiry must be 0 (never) or in the future")]
+    InvalidExpiry,
+    #[msg("TTL must be non-negative")]
+    InvalidTtl,
+    #[msg("Page limit exceeds the maximum entries per read")]
+    PageLimitExceeded,
+    #[msg("Schema id is not registered in this registry")]
+    UnknownSchema,
+    #[msg("Schema id must be non-zero and not already registered")]
+    InvalidSchema,
+    #[msg("Schema allow-list is full")]
+    SchemaListFull,
+    #[msg("Registry changed since the expected digest was read")]
+    StateChanged,
+    #[msg("A derived field does not match the entries it is derived from")]
+    InconsistentDerivedState,
+    #[msg("Entry belongs to a sealed epoch and can no longer be modified")]
+    EpochSealed,
+    #[msg("Only the current epoch can be sealed")]
+    InvalidEpoch,
+    #[msg("Histogram needs a positive bucket width and at least one bucket")]
+    InvalidHistogram,
+    #[msg("Registry lifetime has expired")]
+    RegistryExpired,
+    #[msg("Registry has not expired and cannot be reaped")]
+    RegistryNotExpired,
+    #[msg("Confidence must be 0-100")]
+    InvalidConfidence,
+    #[msg("Too many entries to scan in one instruction")]
+    ScanTooLarge,
+    #[msg("Clock sysvar is unavailable in this execution context")]
+    ClockUnavailable,
+    #[msg("Content exceeds the maximum length for on-chain hashing")]
+    ContentTooLong,
+    #[msg("This registry requires the co-authority to co-sign destructive operations")]
+    CoAuthorityRequired,
+    #[msg("Entry is immutable and can no longer be modified")]
+    EntryImmutable,
+    #[msg("Memory id must be non-zero")]
+    InvalidMemoryId,
+    #[msg("Memory id is already used by another entry")]
+    DuplicateMemoryId,
+    #[msg("Every write-grant slot is in use")]
+    WriteGrantsFull,
+    #[msg("No outstanding write grant for this key")]
+    WriteGrantNotFound,
+    #[msg("Write grants cannot be issued to the default pubkey")]
+    InvalidGrantee,
+    #[msg("Episodic ring is full and holds no removable entry")]
+    RingFull,
+    #[msg("Decay period must be zero (disabled) or positive")]
+    InvalidDecayPolicy,
+    #[msg("Retention limit reached and every entry is sealed or immutable")]
+    RetentionFull,
+    #[msg("Unknown sort key")]
+    InvalidSortKey,
+    #[msg("An encryption nonce is only allowed on an encrypted entry")]
+    NonceWithoutEncryption,
+    #[msg("Invalid content kind (must be 0-4)")]
+    InvalidContentKind,
+    #[msg("Account balance is below the rent-exempt minimum after growth")]
+    NotRentExempt,
+    #[msg("This registry only accepts encrypted memories")]
+    EncryptionRequired,
+    #[msg("Prefix length must be 1-32 bytes")]
+    InvalidPrefixLength,
+    #[msg("Direction must be NEIGHBOR_PREVIOUS (0) or NEIGHBOR_NEXT (1)")]
+    InvalidDirection,
+    #[msg("Entry is quarantined pending review")]
+    EntryQuarantined,
+    #[msg("Timestamp is not signed by the registry's time oracle")]
+    InvalidTimestampSignature,
+    #[msg("Registry is frozen by its circuit breaker; the authority must unfreeze it")]
+    RegistryFrozen,
+    #[msg("Circuit breaker window must be positive when a threshold is set")]
+    InvalidCircuitBreaker,
+    #[msg("Export was written in an unsupported canonical format version")]
+    UnsupportedExportVersion,
+    #[msg("At most 4 provenance sources per memory")]
+    TooManySources,
+    #[msg("A cited provenance source is not registered")]
+    SourceMissing,
+    #[msg("Registry account is too small; grow it with grow_registry first")]
+    RegistryTooSmall,
+    #[msg("Registry layout version is not one migrate_registry can convert")]
+    UnsupportedSchemaVersion,
+    #[msg("Attested timestamp is later than the cluster clock allows")]
+    TimestampInFuture,
 }
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+/// Emitted before a registration is validated. A client that crashed
+/// mid-batch matches these against `MemoryRegistered` by `request_id`: an
+/// intent with no matching success was rejected (its transaction's logs
+/// end in the error) or never landed.
+#[event]
+pub struct RegisterIntent {
+    /// Client-supplied correlation id; 0 if none was given.
+    pub request_id: u64,
+    pub content_hash: [u8; 32],
+}
+
+/// Emitted once a registration has been applied.
+#[event]
+pub struct MemoryRegistered {
+    pub request_id: u64,
+    pub content_hash: [u8; 32],
+    pub memory_id: u64,
+    /// False when the duplicate policy turned the call into a no-op.
+    pub written: bool,
+}
+
+/// Emitted when a burst of registrations trips the circuit breaker and
+/// freezes the registry.
+#[event]
+pub struct CircuitBreakerTripped {
+    /// Registrations counted in the window that tripped it.
+    pub burst_count: u32,
+    pub burst_window_start: i64,
+    pub tripped_at: i64,
+}
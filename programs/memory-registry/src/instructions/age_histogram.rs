@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use crate::errors::RegistryError;
+use super::read_registry::ReadRegistry;
+
+pub(crate) fn handler(
+    ctx: Context<ReadRegistry>,
+    bucket_seconds: i64,
+    num_buckets: u8,
+) -> Result<Vec<u32>> {
+    // A u8 bucket count always fits the return buffer (255 * 4 + 4 bytes).
+    require!(
+        bucket_seconds > 0 && num_buckets > 0,
+        RegistryError::InvalidHistogram
+    );
+
+    let registry = &ctx.accounts.registry;
+    let now = unix_now()?;
+
+    // Entries older than the last bucket are folded into it, so the counts
+    // always sum to the live entry count. Future timestamps land in bucket 0.
+    let last = num_buckets as usize - 1;
+    let mut buckets = vec![0u32; num_buckets as usize];
+    for entry in registry.entries.iter().filter(|e| e.is_live()) {
+        let age = now.saturating_sub(entry.timestamp).max(0);
+        let bucket = ((age / bucket_seconds) as usize).min(last);
+        buckets[bucket] += 1;
+    }
+
+    Ok(buckets)
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use crate::errors::RegistryError;
+use super::mutate_entry::MutateEntry;
+
+pub(crate) fn handler(
+    ctx: Context<MutateEntry>,
+    content_hash: [u8; 32],
+    cluster_id: u16,
+) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+
+    let index = registry
+        .find_entry(&content_hash)
+        .ok_or(RegistryError::HashNotFound)?;
+    registry.require_mutable(index)?;
+
+    registry.entries[index].cluster_id = cluster_id;
+    registry.touch_entry(index, unix_now()?);
+
+    msg!(
+        "Cluster assigned: memory_id={} cluster_id={}",
+        registry.entries[index].memory_id,
+        cluster_id
+    );
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::{MemoryRegistry, ReplaceStaging};
+
+#[derive(Accounts)]
+pub struct CancelReplace<'info> {
+    #[account(
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    #[account(
+        mut,
+        seeds = [b"replace-staging", registry.key().as_ref()],
+        bump = staging.bump,
+        has_one = registry,
+        close = authority,
+    )]
+    pub staging: Account<'info, ReplaceStaging>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(_ctx: Context<CancelReplace>) -> Result<()> {
+    // Closing the staging account (and refunding its rent) is all there is.
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+use crate::errors::RegistryError;
+
+#[derive(Accounts)]
+pub struct CancelReservation<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<CancelReservation>, memory_id: u64) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+
+    let index = registry
+        .find_reservation(memory_id)
+        .ok_or(RegistryError::ReservationNotFound)?;
+
+    // The freed slot stays allocated for the next registration.
+    registry.remove_entry(index);
+
+    msg!("Reservation cancelled: memory_id={}", memory_id);
+    Ok(())
+}
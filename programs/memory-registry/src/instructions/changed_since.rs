@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+use crate::state::MemoryEntry;
+use super::read_registry::ReadRegistry;
+
+pub(crate) fn handler(
+    ctx: Context<ReadRegistry>,
+    since_ts: i64,
+    start: u32,
+    limit: u32,
+) -> Result<Vec<MemoryEntry>> {
+    ctx.accounts
+        .registry
+        .page_where(start, limit, |e| e.last_modified > since_ts)
+}
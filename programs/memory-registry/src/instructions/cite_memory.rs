@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::clock::unix_now;
 use crate::state::{Pool, PoolMemoryRecord, CitationRecord};
 use crate::errors::RegistryError;
 
@@ -46,7 +47,7 @@ pub struct CiteMemory<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<CiteMemory>) -> Result<()> {
+pub(crate) fn handler(ctx: Context<CiteMemory>) -> Result<()> {
     // Disallow self-citation (author citing own memory)
     require!(
         ctx.accounts.citer.key() != ctx.accounts.memory.author,
@@ -75,7 +76,7 @@ pub fn handler(ctx: Context<CiteMemory>) -> Result<()> {
     citation.memory_pda = ctx.accounts.memory.key();
     citation.citer = ctx.accounts.citer.key();
     citation.author = ctx.accounts.memory.author;
-    citation.timestamp = Clock::get()?.unix_timestamp;
+    citation.timestamp = unix_now()?;
     citation.royalty_paid = royalty;
     citation.bump = ctx.bumps.citation;
     citation._reserved = [0; 8];
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::account_size::shrink_registry;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+
+#[derive(Accounts)]
+pub struct ClearEntries<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Must sign as well when the registry has a co-authority set.
+    pub co_authority: Option<Signer<'info>>,
+}
+
+pub(crate) fn handler(ctx: Context<ClearEntries>) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    registry.require_co_signed(ctx.accounts.co_authority.as_ref())?;
+
+    // An empty replacement resets every derived field and refuses while
+    // any sealed or immutable entry remains. Settings are left untouched.
+    let cleared = registry.entries.len();
+    registry.replace_entries(Vec::new())?;
+
+    let refunded = shrink_registry(
+        &ctx.accounts.registry,
+        &ctx.accounts.authority.to_account_info(),
+        MemoryRegistry::INITIAL_CAPACITY,
+    )?;
+
+    msg!("Registry cleared: removed={} refunded={}", cleared, refunded);
+    Ok(())
+}
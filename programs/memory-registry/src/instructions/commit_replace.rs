@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::account_size::{require_capacity, shrink_registry};
+use crate::pda::REGISTRY_SEED;
+use crate::state::{MemoryRegistry, ReplaceStaging};
+
+#[derive(Accounts)]
+pub struct CommitReplace<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    #[account(
+        mut,
+        seeds = [b"replace-staging", registry.key().as_ref()],
+        bump = staging.bump,
+        has_one = registry,
+        close = authority,
+    )]
+    pub staging: Account<'info, ReplaceStaging>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Must sign as well when the registry has a co-authority set.
+    pub co_authority: Option<Signer<'info>>,
+}
+
+pub(crate) fn handler(ctx: Context<CommitReplace>, expected_digest: [u8; 32]) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    registry.require_co_signed(ctx.accounts.co_authority.as_ref())?;
+
+    // Optimistic concurrency: the caller must have seen the current state.
+    registry.require_sync_token(&expected_digest)?;
+
+    // A staged set can need more growth than one instruction may realloc,
+    // so the account must already fit it (see `grow_registry`).
+    let entries = std::mem::take(&mut ctx.accounts.staging.entries);
+    let replaced = entries.len();
+    require_capacity(&registry.to_account_info(), replaced)?;
+    registry.replace_entries(entries)?;
+
+    let refunded = shrink_registry(
+        &ctx.accounts.registry,
+        &ctx.accounts.authority.to_account_info(),
+        replaced,
+    )?;
+
+    msg!(
+        "Registry replaced from staging: {} entries refunded={}",
+        replaced,
+        refunded
+    );
+    Ok(())
+}
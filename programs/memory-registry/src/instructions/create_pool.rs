@@ -20,7 +20,7 @@ pub struct CreatePool<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<CreatePool>,
     namespace: [u8; 32],
     write_fee: u64,
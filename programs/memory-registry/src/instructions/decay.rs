@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use crate::pda::REGISTRY_SEED;
+use crate::scan::WorkBudget;
+use crate::state::MemoryRegistry;
+
+#[derive(Accounts)]
+pub struct Decay<'info> {
+    /// Permissionless: anyone may crank decay, so no signer is required.
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, registry.authority.as_ref()],
+        bump = registry.bump,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+}
+
+/// Outcome of a `decay` call, returned via return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DecayResult {
+    /// Entries whose importance tier was lowered in this call.
+    pub decayed: u32,
+    /// Entry index to pass as `cursor` on the next call.
+    pub next_cursor: u32,
+    /// True once the scan has reached the end of `entries`.
+    pub done: bool,
+}
+
+pub(crate) fn handler(ctx: Context<Decay>, cursor: u32, max_work: u32) -> Result<DecayResult> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+
+    let period = registry.decay_after_seconds;
+    let len = registry.entries.len();
+    if period == 0 {
+        return Ok(DecayResult {
+            decayed: 0,
+            next_cursor: len as u32,
+            done: true,
+        });
+    }
+
+    let now = unix_now()?;
+    let mut budget = WorkBudget::new(max_work);
+    let mut index = cursor as usize;
+    let mut decayed: u32 = 0;
+    while index < len && budget.tick() {
+        decayed += decay_entry(registry, index, now, period) as u32;
+        index += 1;
+    }
+
+    let done = index >= len;
+    msg!(
+        "Decay: decayed={} next_cursor={} done={}",
+        decayed,
+        index,
+        done
+    );
+    Ok(DecayResult {
+        decayed,
+        next_cursor: index as u32,
+        done,
+    })
+}
+
+/// Apply the decay the entry at `index` owes at `now`: one tier per full
+/// `period` since it was last modified, less the `decay_steps` already paid,
+/// so re-running the scan never decays the same period twice. Pending,
+/// sealed and immutable entries are skipped. Returns whether the tier
+/// dropped.
+///
+/// Decay is recorded in the nonce but doesn't stamp `last_modified`: that
+/// would restart the clock it is measured from, and `changed_since` would
+/// report every decayed entry as if a writer had touched it.
+pub fn decay_entry(registry: &mut MemoryRegistry, index: usize, now: i64, period: i64) -> bool {
+    let entry = &registry.entries[index];
+    let owed = (now.saturating_sub(entry.last_modified) / period).clamp(0, u8::MAX as i64) as u8;
+    if entry.is_pending() || owed <= entry.decay_steps || registry.require_mutable(index).is_err() {
+        return false;
+    }
+    let tier = entry.importance_tier.saturating_sub(owed - entry.decay_steps);
+    let lowered = tier != entry.importance_tier;
+    if lowered {
+        registry.set_importance_tier(index, tier);
+    }
+    registry.entries[index].decay_steps = owed;
+    lowered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MemoryEntry;
+
+    const PERIOD: i64 = 100;
+
+    fn registry_with(timestamp: i64, last_modified: i64) -> MemoryRegistry {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        registry.push_entry(MemoryEntry {
+            content_hash: [1; 32],
+            importance_tier: 2,
+            timestamp,
+            last_modified,
+            ..Default::default()
+        });
+        registry
+    }
+
+    #[test]
+    fn decays_from_last_modified_not_creation() {
+        // Created long ago but modified recently: nothing owed yet.
+        let mut registry = registry_with(0, 950);
+        assert!(!decay_entry(&mut registry, 0, 1_000, PERIOD));
+        assert_eq!(registry.entries[0].importance_tier, 2);
+
+        assert!(decay_entry(&mut registry, 0, 1_050, PERIOD));
+        assert_eq!(registry.entries[0].importance_tier, 1);
+        assert_eq!(registry.tier_counts, [0, 1, 0]);
+    }
+
+    #[test]
+    fn decay_is_idempotent_and_leaves_last_modified_alone() {
+        let mut registry = registry_with(0, 0);
+        let nonce = registry.nonce;
+        assert!(decay_entry(&mut registry, 0, 150, PERIOD));
+        assert!(!decay_entry(&mut registry, 0, 199, PERIOD), "same period twice");
+        assert_eq!(registry.entries[0].last_modified, 0);
+        assert_ne!(registry.nonce, nonce);
+
+        assert!(decay_entry(&mut registry, 0, 250, PERIOD));
+        assert_eq!(registry.entries[0].importance_tier, 0);
+        assert!(!decay_entry(&mut registry, 0, 10_000, PERIOD), "already at the floor");
+    }
+
+    #[test]
+    fn a_touch_restarts_the_decay_clock() {
+        let mut registry = registry_with(0, 0);
+        assert!(decay_entry(&mut registry, 0, 150, PERIOD));
+        registry.touch_entry(0, 500);
+        assert_eq!(registry.entries[0].decay_steps, 0);
+        assert!(!decay_entry(&mut registry, 0, 550, PERIOD));
+        assert!(decay_entry(&mut registry, 0, 600, PERIOD));
+        assert_eq!(registry.entries[0].importance_tier, 0);
+    }
+}
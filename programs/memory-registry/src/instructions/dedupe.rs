@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use crate::account_size::shrink_registry;
+use crate::scan::WorkBudget;
+use crate::state::MemoryRegistry;
+use super::prune_and_compact::PruneAndCompact;
+
+/// Outcome of a `dedupe` call, returned via return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DedupeResult {
+    /// Duplicate entries removed in this call.
+    pub removed: u32,
+    /// Position in `sorted_index` to pass as `cursor` on the next call.
+    pub next_cursor: u32,
+    /// True once the scan has reached the end of `sorted_index`.
+    pub done: bool,
+}
+
+pub(crate) fn handler(
+    ctx: Context<PruneAndCompact>,
+    cursor: u32,
+    max_work: u32,
+) -> Result<DedupeResult> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    registry.require_co_signed(ctx.accounts.co_authority.as_ref())?;
+
+    let mut budget = WorkBudget::new(max_work);
+    let (removed, pos) = dedupe_sorted(registry, cursor, || budget.tick());
+
+    let remaining = registry.entries.len();
+    let done = pos as usize + 1 >= registry.sorted_index.len();
+    let refund = shrink_registry(
+        &ctx.accounts.registry,
+        &ctx.accounts.authority.to_account_info(),
+        remaining,
+    )?;
+
+    msg!(
+        "Deduped: removed={} next_cursor={} done={} refund={}",
+        removed,
+        pos,
+        done,
+        refund
+    );
+    Ok(DedupeResult {
+        removed,
+        next_cursor: pos,
+        done,
+    })
+}
+
+/// Remove duplicate hashes from `sorted_index` position `cursor` on, one
+/// adjacent pair per `tick`. Returns the removed count and the position to
+/// resume from.
+///
+/// Duplicates sit next to each other in `sorted_index`, so each step
+/// compares one adjacent pair. Removing an entry shrinks `sorted_index`
+/// under the cursor, so the position only advances when nothing was
+/// removed. Pending reservations share the zero hash and are not
+/// duplicates of each other.
+pub fn dedupe_sorted(
+    registry: &mut MemoryRegistry,
+    cursor: u32,
+    mut tick: impl FnMut() -> bool,
+) -> (u32, u32) {
+    let mut pos = cursor as usize;
+    let mut removed: u32 = 0;
+    while pos + 1 < registry.sorted_index.len() && tick() {
+        let first = registry.sorted_index[pos] as usize;
+        let second = registry.sorted_index[pos + 1] as usize;
+        let (a, b) = (&registry.entries[first], &registry.entries[second]);
+        if a.is_pending() || a.content_hash != b.content_hash {
+            pos += 1;
+            continue;
+        }
+
+        // Keep the earliest; a sealed or immutable copy is never removed.
+        let later = if b.timestamp >= a.timestamp { second } else { first };
+        if registry.require_mutable(later).is_ok() {
+            registry.remove_entry(later);
+            removed += 1;
+        } else {
+            pos += 1;
+        }
+    }
+    (removed, pos as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MemoryEntry;
+
+    /// A registry holding `(hash, timestamp)` entries as given, duplicates
+    /// included: the write paths refuse to create them, so `entries` is set
+    /// directly and the derived state rebuilt.
+    fn registry_with(entries: &[(u8, i64)]) -> MemoryRegistry {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        registry.entries = entries
+            .iter()
+            .map(|&(hash, timestamp)| MemoryEntry {
+                content_hash: [hash; 32],
+                timestamp,
+                ..Default::default()
+            })
+            .collect();
+        registry.rebuild_derived();
+        registry
+    }
+
+    fn left(registry: &MemoryRegistry) -> Vec<(u8, i64)> {
+        let entries = registry.entries.iter();
+        entries.map(|e| (e.content_hash[0], e.timestamp)).collect()
+    }
+
+    #[test]
+    fn keeps_only_the_earliest_copy() {
+        let mut registry = registry_with(&[(1, 30), (2, 5), (1, 10), (1, 20)]);
+        let (removed, _) = dedupe_sorted(&mut registry, 0, || true);
+
+        assert_eq!(removed, 2);
+        assert_eq!(left(&registry), vec![(2, 5), (1, 10)]);
+        assert_eq!(registry.memory_count, 2);
+        assert_eq!(registry.derived_mismatch(), None);
+    }
+
+    #[test]
+    fn an_immutable_later_copy_is_kept() {
+        let mut registry = registry_with(&[(1, 10), (1, 20), (2, 5), (2, 8)]);
+        registry.entries[1].set_flag(MemoryEntry::FLAG_IMMUTABLE, true);
+        let (removed, next_cursor) = dedupe_sorted(&mut registry, 0, || true);
+
+        assert_eq!(removed, 1);
+        assert_eq!(left(&registry), vec![(1, 10), (1, 20), (2, 5)]);
+        assert_eq!(next_cursor as usize + 1, registry.sorted_index.len());
+    }
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::state::MemoryRegistry;
+use super::read_registry::ReadRegistry;
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>) -> Result<u16> {
+    Ok(diversity_bps(&ctx.accounts.registry))
+}
+
+/// Normalized type entropy of the live entries, in basis points. Counted
+/// by scan: `type_counts` includes quarantined entries.
+pub fn diversity_bps(registry: &MemoryRegistry) -> u16 {
+    let mut type_counts = [0u32; 4];
+    for entry in registry.entries.iter().filter(|e| e.is_live()) {
+        type_counts[entry.memory_type as usize] += 1;
+    }
+
+    let total: u64 = type_counts.iter().map(|&n| n as u64).sum();
+    if total == 0 {
+        return 0;
+    }
+
+    // Shannon entropy of the type distribution, divided by its maximum
+    // (ln of the number of types) so a perfectly even spread scores 1.
+    let entropy: f64 = type_counts
+        .iter()
+        .filter(|&&n| n > 0)
+        .map(|&n| {
+            let p = n as f64 / total as f64;
+            -p * p.ln()
+        })
+        .sum();
+    let normalized = entropy / (type_counts.len() as f64).ln();
+
+    (normalized * 10_000.0).round().clamp(0.0, 10_000.0) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MemoryEntry;
+
+    fn registry_of(types: &[(u8, u16)]) -> MemoryRegistry {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        for (i, &(memory_type, flags)) in types.iter().enumerate() {
+            registry.push_entry(MemoryEntry {
+                content_hash: [i as u8 + 1; 32],
+                memory_type,
+                flags,
+                ..Default::default()
+            });
+        }
+        registry
+    }
+
+    #[test]
+    fn even_split_scores_full_and_one_type_scores_zero() {
+        assert_eq!(diversity_bps(&registry_of(&[(0, 0), (1, 0), (2, 0), (3, 0)])), 10_000);
+        assert_eq!(diversity_bps(&registry_of(&[(2, 0), (2, 0)])), 0);
+        assert_eq!(diversity_bps(&registry_of(&[])), 0);
+    }
+
+    #[test]
+    fn quarantined_entries_do_not_count() {
+        let q = MemoryEntry::FLAG_QUARANTINED;
+        let registry = registry_of(&[(0, 0), (1, q), (2, q), (3, q)]);
+        assert_eq!(diversity_bps(&registry), 0);
+    }
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::state::MemoryRegistry;
+use super::read_registry::ReadRegistry;
+
+/// Encryption coverage of the live entries, returned via return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EncryptionCoverage {
+    /// Live entries with the encrypted flag set.
+    pub encrypted: u32,
+    /// Live entries (pending reservations excluded).
+    pub total: u32,
+    /// `encrypted / total` in basis points (0-10_000); 0 for an empty registry.
+    pub encrypted_bps: u16,
+}
+
+impl EncryptionCoverage {
+    /// One scan over the live entries. `encrypted_count` also counts
+    /// quarantined entries, so it can't stand in for this.
+    pub fn of(registry: &MemoryRegistry) -> Self {
+        let (mut encrypted, mut total) = (0u32, 0u32);
+        for entry in registry.entries.iter().filter(|e| e.is_live()) {
+            total += 1;
+            encrypted += entry.is_encrypted() as u32;
+        }
+        let encrypted_bps = if total == 0 {
+            0
+        } else {
+            (encrypted as u64 * 10_000 / total as u64) as u16
+        };
+        EncryptionCoverage {
+            encrypted,
+            total,
+            encrypted_bps,
+        }
+    }
+}
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>) -> Result<EncryptionCoverage> {
+    Ok(EncryptionCoverage::of(&ctx.accounts.registry))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MemoryEntry;
+
+    #[test]
+    fn coverage_ignores_quarantined_and_pending() {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        for (hash, flags) in [
+            (1, MemoryEntry::FLAG_ENCRYPTED),
+            (2, 0),
+            (3, MemoryEntry::FLAG_ENCRYPTED | MemoryEntry::FLAG_QUARANTINED),
+            (0, MemoryEntry::FLAG_PENDING),
+        ] {
+            registry.push_entry(MemoryEntry {
+                content_hash: [hash; 32],
+                flags,
+                ..Default::default()
+            });
+        }
+
+        let coverage = EncryptionCoverage::of(&registry);
+        assert_eq!((coverage.encrypted, coverage.total), (1, 2));
+        assert_eq!(coverage.encrypted_bps, 5_000);
+        assert_eq!(registry.encrypted_count, 2, "the counter still has it");
+    }
+
+    #[test]
+    fn empty_registry_has_zero_coverage() {
+        let registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        assert_eq!(EncryptionCoverage::of(&registry).encrypted_bps, 0);
+    }
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use super::read_registry::ReadRegistry;
+
+/// Expiry coverage of the live entries at the current clock, returned via
+/// return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ExpiredCoverage {
+    /// Live entries whose `expires_at` has passed.
+    pub expired: u32,
+    /// Of those, entries `sweep` would actually remove: sealed and
+    /// immutable entries are skipped by it.
+    pub reclaimable: u32,
+    /// Live entries (`MemoryEntry::is_live`).
+    pub total: u32,
+    /// `expired / total` in basis points (0-10_000); 0 for an empty registry.
+    pub expired_bps: u16,
+}
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>) -> Result<ExpiredCoverage> {
+    let registry = &ctx.accounts.registry;
+    let now = unix_now()?;
+
+    let (mut total, mut expired, mut reclaimable) = (0u32, 0u32, 0u32);
+    for (index, entry) in registry.entries.iter().enumerate() {
+        if !entry.is_live() {
+            continue;
+        }
+        total += 1;
+        if !entry.is_expired(now) {
+            continue;
+        }
+        expired += 1;
+        if registry.require_mutable(index).is_ok() {
+            reclaimable += 1;
+        }
+    }
+
+    let expired_bps = if total == 0 {
+        0
+    } else {
+        (expired as u64 * 10_000 / total as u64) as u16
+    };
+
+    Ok(ExpiredCoverage {
+        expired,
+        reclaimable,
+        total,
+        expired_bps,
+    })
+}
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::MAX_RETURN_DATA;
+use crate::state::{MemoryEntry, MemoryRegistry};
+use crate::errors::RegistryError;
+use super::read_registry::ReadRegistry;
+
+/// Version of the `CanonicalExport` format. Bump it whenever `MemoryEntry`'s
+/// Borsh layout changes, so older backups stay identifiable.
+pub const CANONICAL_FORMAT_VERSION: u16 = 1;
+
+/// Borsh size of the `CanonicalExport` fields before `entries`, plus the
+/// entries Vec prefix.
+const CANONICAL_HEADER_SIZE: usize = 2 + 2 + 4 + 4 + 4;
+
+/// Most entries one `export_canonical` page can carry in return data.
+pub const MAX_EXPORT_ENTRIES: usize =
+    (MAX_RETURN_DATA - CANONICAL_HEADER_SIZE) / MemoryRegistry::ENTRY_SIZE;
+
+/// One page of a versioned registry backup. The leading fields describe the
+/// layout, so a reader can tell which `MemoryEntry` format follows before
+/// decoding it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CanonicalExport {
+    /// `CANONICAL_FORMAT_VERSION` at export time.
+    pub format_version: u16,
+    /// Borsh size of each entry in `entries`.
+    pub entry_size: u16,
+    /// Slots in the registry; pages are complete once `start` reaches it.
+    pub total: u32,
+    /// Slot this page starts at: the requested `start`, clamped to `total`.
+    pub start: u32,
+    /// Entries in slots `start..start + limit`, in insertion order. Pending
+    /// reservations are left out.
+    pub entries: Vec<MemoryEntry>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<ReadRegistry>,
+    start: u32,
+    limit: u32,
+) -> Result<CanonicalExport> {
+    export_page(&ctx.accounts.registry, start, limit)
+}
+
+/// The `export_canonical` page of `registry` at `start`, which is clamped
+/// to the slot count so `start` in the page is where its entries begin.
+pub fn export_page(registry: &MemoryRegistry, start: u32, limit: u32) -> Result<CanonicalExport> {
+    require!(
+        limit as usize <= MAX_EXPORT_ENTRIES,
+        RegistryError::PageLimitExceeded
+    );
+
+    let len = registry.entries.len();
+    let begin = (start as usize).min(len);
+    let end = begin.saturating_add(limit as usize).min(len);
+    let entries = registry.entries[begin..end]
+        .iter()
+        .filter(|entry| !entry.is_pending())
+        .cloned()
+        .collect();
+
+    Ok(CanonicalExport {
+        format_version: CANONICAL_FORMAT_VERSION,
+        entry_size: MemoryRegistry::ENTRY_SIZE as u16,
+        total: len as u32,
+        start: begin as u32,
+        entries,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::import_canonical::check_export_format;
+
+    fn registry_of(hashes: &[u8]) -> MemoryRegistry {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        for &h in hashes {
+            registry.push_entry(MemoryEntry {
+                content_hash: [h; 32],
+                memory_type: h % 4,
+                timestamp: 100 + h as i64,
+                last_modified: 100 + h as i64,
+                memory_id: h as u64,
+                ..Default::default()
+            });
+        }
+        registry
+    }
+
+    #[test]
+    fn a_paged_backup_restores_the_same_entries() {
+        let mut registry = registry_of(&[4, 1, 3, 5, 2]);
+        registry.push_entry(MemoryEntry {
+            flags: MemoryEntry::FLAG_PENDING,
+            ..Default::default()
+        });
+
+        let mut restored = Vec::new();
+        let mut start = 0;
+        loop {
+            let page = export_page(&registry, start, 2).unwrap();
+            let bytes = page.try_to_vec().unwrap();
+            let page = CanonicalExport::try_from_slice(&bytes).unwrap();
+            check_export_format(&page).unwrap();
+            assert_eq!((page.start, page.total), (start, 6));
+            restored.extend(page.entries);
+            start += 2;
+            if start >= page.total {
+                break;
+            }
+        }
+        assert_eq!(restored.len(), 5, "the pending reservation is left out");
+
+        // Restore over a registry that has since lost an entry. A replace
+        // stamps `last_modified`, and `timestamp` too for a hash the
+        // registry no longer holds; every other field comes back as exported.
+        let mut expected: Vec<Vec<u8>> = Vec::new();
+        for entry in &registry.entries[..5] {
+            let mut entry = entry.clone();
+            entry.last_modified = 2_000;
+            if entry.content_hash == [4; 32] {
+                entry.timestamp = 2_000;
+            }
+            expected.push(entry.try_to_vec().unwrap());
+        }
+        registry.remove_entry(0);
+        registry.replace_entries_at(restored, 2_000).unwrap();
+
+        let entries: Vec<Vec<u8>> =
+            registry.entries.iter().map(|e| e.try_to_vec().unwrap()).collect();
+        assert_eq!(entries, expected);
+        assert_eq!(registry.find_entry(&[3; 32]), Some(2));
+    }
+
+    #[test]
+    fn the_page_start_is_clamped_to_the_slot_count() {
+        let registry = registry_of(&[1, 2, 3]);
+        let page = export_page(&registry, 10, 5).unwrap();
+        assert_eq!((page.start, page.total), (3, 3));
+        assert!(page.entries.is_empty());
+
+        let result = export_page(&registry, 0, MAX_EXPORT_ENTRIES as u32 + 1);
+        assert_eq!(result.err(), Some(RegistryError::PageLimitExceeded.into()));
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::state::MemoryEntry;
+use crate::errors::RegistryError;
+use super::read_registry::ReadRegistry;
+
+/// `filter` argument value matching any memory type / importance tier.
+pub const FILTER_ANY: u8 = u8::MAX;
+
+pub(crate) fn handler(
+    ctx: Context<ReadRegistry>,
+    memory_type: u8,
+    importance_tier: u8,
+    start: u32,
+    limit: u32,
+) -> Result<Vec<MemoryEntry>> {
+    require!(
+        memory_type == FILTER_ANY || memory_type <= 3,
+        RegistryError::InvalidMemoryType
+    );
+    require!(
+        importance_tier == FILTER_ANY || importance_tier <= 2,
+        RegistryError::InvalidImportanceTier
+    );
+
+    ctx.accounts.registry.page_where(start, limit, |e| {
+        (memory_type == FILTER_ANY || e.memory_type == memory_type)
+            && (importance_tier == FILTER_ANY || e.importance_tier == importance_tier)
+    })
+}
@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::clock::unix_now;
+use crate::state::MemoryRegistry;
+use crate::errors::RegistryError;
+use super::register_memory::{check_insert_policy, check_new_entry};
+
+#[derive(Accounts)]
+pub struct FinalizeMemory<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<FinalizeMemory>,
+    memory_id: u64,
+    content_hash: [u8; 32],
+    memory_type: u8,
+    importance_tier: u8,
+    encrypted: bool,
+) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    registry.require_unfrozen()?;
+    let now = unix_now()?;
+    finalize_reservation(
+        registry,
+        memory_id,
+        content_hash,
+        memory_type,
+        importance_tier,
+        encrypted,
+        now,
+    )?;
+
+    msg!("Memory finalized: memory_id={}", memory_id);
+    Ok(())
+}
+
+/// Body of `finalize_memory`: the same checks and limits as a fresh
+/// registration, then the reservation becomes a live entry. The reservation
+/// wasn't counted yet, so it takes a new slot; being pending, it is never
+/// itself evicted.
+pub(crate) fn finalize_reservation(
+    registry: &mut MemoryRegistry,
+    memory_id: u64,
+    content_hash: [u8; 32],
+    memory_type: u8,
+    importance_tier: u8,
+    encrypted: bool,
+    now: i64,
+) -> Result<()> {
+    check_new_entry(registry, &content_hash, memory_type, importance_tier, encrypted)?;
+    require!(
+        registry.find_reservation(memory_id).is_some(),
+        RegistryError::ReservationNotFound
+    );
+    require!(
+        registry.find_entry(&content_hash).is_none(),
+        RegistryError::DuplicateHash
+    );
+    check_insert_policy(registry, &content_hash, memory_type, None)?;
+
+    // Evictions shift indices, so find the reservation again.
+    let index = registry
+        .find_reservation(memory_id)
+        .ok_or(RegistryError::ReservationNotFound)?;
+    registry.finalize_entry(
+        index,
+        content_hash,
+        memory_type,
+        importance_tier,
+        encrypted,
+        now,
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MemoryEntry;
+
+    fn registry_with(episodic: &[u8]) -> MemoryRegistry {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        for &h in episodic {
+            registry.push_entry(MemoryEntry {
+                content_hash: [h; 32],
+                timestamp: h as i64,
+                ..Default::default()
+            });
+        }
+        let mut reservation = MemoryEntry {
+            memory_id: 42,
+            ..Default::default()
+        };
+        reservation.set_flag(MemoryEntry::FLAG_PENDING, true);
+        registry.push_entry(reservation);
+        registry
+    }
+
+    fn finalize(registry: &mut MemoryRegistry, hash: u8, memory_type: u8) -> Result<()> {
+        finalize_reservation(registry, 42, [hash; 32], memory_type, 1, false, 1_000)
+    }
+
+    #[test]
+    fn finalize_makes_ring_room() {
+        let mut registry = registry_with(&[1, 2, 3]);
+        registry.ring_capacity = 3;
+        finalize(&mut registry, 9, 0).unwrap();
+        assert_eq!(registry.type_counts[0], 3);
+        assert!(registry.find_entry(&[1; 32]).is_none(), "oldest evicted");
+        let index = registry.find_entry(&[9; 32]).unwrap();
+        assert_eq!(registry.entries[index].memory_id, 42);
+        assert!(!registry.entries[index].is_pending());
+        assert_eq!(registry.derived_mismatch(), None);
+    }
+
+    #[test]
+    fn finalize_honours_max_retained() {
+        let mut registry = registry_with(&[1, 2]);
+        registry.max_retained = 2;
+        finalize(&mut registry, 9, 1).unwrap();
+        assert_eq!(registry.stored_count(), 2);
+        assert!(registry.find_entry(&[1; 32]).is_none());
+        assert!(registry.find_entry(&[9; 32]).is_some());
+    }
+
+    #[test]
+    fn finalize_enforces_type_caps_and_duplicates() {
+        let mut registry = registry_with(&[1]);
+        registry.type_caps[0] = 1;
+        assert!(finalize(&mut registry, 9, 0).is_err());
+        assert!(finalize(&mut registry, 1, 1).is_err(), "duplicate hash");
+        assert!(finalize(&mut registry, 0, 1).is_err(), "zero hash");
+        finalize(&mut registry, 9, 1).unwrap();
+        assert!(finalize(&mut registry, 8, 1).is_err(), "reservation used up");
+    }
+}
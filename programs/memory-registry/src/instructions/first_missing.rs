@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::MemoryRegistry;
+use super::read_registry::ReadRegistry;
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>, hashes: Vec<[u8; 32]>) -> Result<Option<u32>> {
+    let registry = &ctx.accounts.registry;
+    registry.require_live()?;
+    Ok(first_missing(registry, &hashes))
+}
+
+/// Index of the first of `hashes` with no active entry in `registry`. Stops
+/// at the first miss; the hashes after it are never looked up.
+pub fn first_missing(registry: &MemoryRegistry, hashes: &[[u8; 32]]) -> Option<u32> {
+    hashes
+        .iter()
+        .position(|hash| registry.find_active(hash).is_none())
+        .map(|index| index as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MemoryEntry;
+
+    fn registry_of(hashes: &[u8]) -> MemoryRegistry {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        for &h in hashes {
+            registry.push_entry(MemoryEntry {
+                content_hash: [h; 32],
+                ..Default::default()
+            });
+        }
+        registry
+    }
+
+    fn hashes(bytes: &[u8]) -> Vec<[u8; 32]> {
+        bytes.iter().map(|&b| [b; 32]).collect()
+    }
+
+    #[test]
+    fn finds_the_gap_wherever_it_is() {
+        let registry = registry_of(&[1, 2, 3]);
+        assert_eq!(first_missing(&registry, &hashes(&[9, 1, 2])), Some(0));
+        assert_eq!(first_missing(&registry, &hashes(&[1, 9, 2, 8])), Some(1));
+        assert_eq!(first_missing(&registry, &hashes(&[1, 2, 9])), Some(2));
+    }
+
+    #[test]
+    fn none_when_every_hash_is_registered() {
+        let registry = registry_of(&[1, 2, 3]);
+        assert_eq!(first_missing(&registry, &hashes(&[3, 1, 2])), None);
+        assert_eq!(first_missing(&registry, &[]), None);
+    }
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::MemoryRegistry;
+use super::read_registry::ReadRegistry;
+
+/// Registry account size + rent snapshot, returned via return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AccountMetrics {
+    /// Current account data length in bytes.
+    pub data_len: u64,
+    /// Entry slots in use.
+    pub used_slots: u32,
+    /// Entry slots the current allocation can hold.
+    pub allocated_slots: u32,
+    /// Lamport balance of the registry account.
+    pub lamports: u64,
+    /// Rent-exempt minimum for the current data length.
+    pub rent_exempt_minimum: u64,
+}
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>) -> Result<AccountMetrics> {
+    let registry = &ctx.accounts.registry;
+    let info = registry.to_account_info();
+    let data_len = info.data_len();
+
+    let allocated_slots =
+        data_len.saturating_sub(MemoryRegistry::BASE_SIZE) / MemoryRegistry::SLOT_SIZE;
+
+    Ok(AccountMetrics {
+        data_len: data_len as u64,
+        used_slots: registry.entries.len() as u32,
+        allocated_slots: allocated_slots as u32,
+        lamports: info.lamports(),
+        rent_exempt_minimum: Rent::get()?.minimum_balance(data_len),
+    })
+}
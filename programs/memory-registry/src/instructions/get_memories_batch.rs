@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::MAX_RETURN_DATA;
+use crate::state::{MemoryEntry, MemoryRegistry};
+use crate::errors::RegistryError;
+use super::read_registry::ReadRegistry;
+
+/// Most hashes one `get_memories_batch` call accepts: each result is an
+/// `Option<MemoryEntry>` (1-byte tag + entry), after the 4-byte Vec prefix.
+pub const MAX_BATCH_LOOKUP: usize =
+    (MAX_RETURN_DATA - 4) / (1 + MemoryRegistry::ENTRY_SIZE);
+
+pub(crate) fn handler(
+    ctx: Context<ReadRegistry>,
+    hashes: Vec<[u8; 32]>,
+) -> Result<Vec<Option<MemoryEntry>>> {
+    require!(
+        hashes.len() <= MAX_BATCH_LOOKUP,
+        RegistryError::PageLimitExceeded
+    );
+
+    let registry = &ctx.accounts.registry;
+    registry.require_live()?;
+    Ok(hashes
+        .iter()
+        .map(|hash| {
+            registry
+                .find_active(hash)
+                .map(|index| registry.entries[index].clone())
+        })
+        .collect())
+}
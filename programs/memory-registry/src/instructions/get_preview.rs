@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+use crate::errors::RegistryError;
+use super::read_registry::ReadRegistry;
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>, content_hash: [u8; 32]) -> Result<[u8; 32]> {
+    let registry = &ctx.accounts.registry;
+    registry.require_live()?;
+
+    let index = registry
+        .find_active(&content_hash)
+        .ok_or(RegistryError::HashNotFound)?;
+
+    Ok(registry.entries[index].preview_hash)
+}
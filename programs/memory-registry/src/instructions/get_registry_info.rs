@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::MemoryRegistry;
+use super::read_registry::ReadRegistry;
+
+/// Registry identity and control settings, returned via return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RegistryInfo {
+    pub authority: Pubkey,
+    /// Canonical PDA bump.
+    pub bump: u8,
+    /// Layout version; see `needs_migration`.
+    pub schema_version: u32,
+    /// Co-signer for destructive operations; `Pubkey::default()` = none.
+    pub co_authority: Pubkey,
+    /// Registry lifetime; 0 = never expires.
+    pub expires_at: i64,
+}
+
+impl RegistryInfo {
+    pub fn of(registry: &MemoryRegistry) -> Self {
+        RegistryInfo {
+            authority: registry.authority,
+            bump: registry.bump,
+            schema_version: registry.schema_version & !MemoryRegistry::VERSION_TAG,
+            co_authority: registry.co_authority,
+            expires_at: registry.expires_at,
+        }
+    }
+}
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>) -> Result<RegistryInfo> {
+    Ok(RegistryInfo::of(&ctx.accounts.registry))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returned_info_decodes_to_the_registry_identity() {
+        let authority = Pubkey::new_unique();
+        let mut registry = MemoryRegistry::new(authority, 253, 1_700_000_000);
+        registry.co_authority = Pubkey::new_unique();
+
+        let bytes = RegistryInfo::of(&registry).try_to_vec().unwrap();
+        let info = RegistryInfo::try_from_slice(&bytes).unwrap();
+        assert_eq!(info.authority, authority);
+        assert_eq!(info.bump, 253);
+        assert_eq!(info.schema_version, MemoryRegistry::SCHEMA_VERSION);
+        assert_eq!(info.co_authority, registry.co_authority);
+        assert_eq!(info.expires_at, 1_700_000_000);
+    }
+}
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use crate::state::MemoryRegistry;
+use super::read_registry::ReadRegistry;
+
+/// How the registry account's bytes are spent, returned via return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StorageEfficiency {
+    /// Account data length in bytes.
+    pub account_bytes: u64,
+    /// Bytes holding stored entries, quarantined ones included: `ENTRY_SIZE`
+    /// each, as the entry layout has no padding. Pending reservations are not
+    /// payload yet.
+    pub payload_bytes: u64,
+    /// Bytes the layout spends around the payload: the header, a
+    /// `sorted_index` slot per entry, and pending reservations' entries.
+    /// None of it is inside a stored entry.
+    pub overhead_bytes: u64,
+    /// Allocated but unused bytes past the serialized registry (realloc
+    /// headroom).
+    pub slack_bytes: u64,
+    /// payload / account_bytes, in basis points (10_000 = every byte is
+    /// payload).
+    pub efficiency_bps: u16,
+}
+
+impl StorageEfficiency {
+    pub fn of(registry: &MemoryRegistry, account_bytes: usize) -> Self {
+        let entries = registry.entries.len();
+        let stored = registry.stored_count() as usize;
+        let payload_bytes = stored * MemoryRegistry::ENTRY_SIZE;
+        let used = MemoryRegistry::space_for(entries);
+        let efficiency_bps = if account_bytes == 0 {
+            0
+        } else {
+            (payload_bytes as u64 * 10_000 / account_bytes as u64) as u16
+        };
+        StorageEfficiency {
+            account_bytes: account_bytes as u64,
+            payload_bytes: payload_bytes as u64,
+            overhead_bytes: (used.min(account_bytes) - payload_bytes) as u64,
+            slack_bytes: account_bytes.saturating_sub(used) as u64,
+            efficiency_bps,
+        }
+    }
+}
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>) -> Result<StorageEfficiency> {
+    let registry = &ctx.accounts.registry;
+    Ok(StorageEfficiency::of(registry, registry.to_account_info().data_len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MemoryEntry;
+
+    #[test]
+    fn splits_the_account_into_payload_overhead_and_slack() {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        for h in 1..=3 {
+            registry.push_entry(MemoryEntry {
+                content_hash: [h; 32],
+                ..Default::default()
+            });
+        }
+        let mut pending = MemoryEntry::default();
+        pending.set_flag(MemoryEntry::FLAG_PENDING, true);
+        registry.push_entry(pending);
+
+        let account_bytes = MemoryRegistry::space_for(10);
+        let report = StorageEfficiency::of(&registry, account_bytes);
+        let payload = 3 * MemoryRegistry::ENTRY_SIZE as u64;
+        assert_eq!(report.payload_bytes, payload);
+        assert_eq!(
+            report.overhead_bytes,
+            (MemoryRegistry::BASE_SIZE
+                + 4 * MemoryRegistry::INDEX_ENTRY_SIZE
+                + MemoryRegistry::ENTRY_SIZE) as u64
+        );
+        assert_eq!(report.slack_bytes, 6 * MemoryRegistry::SLOT_SIZE as u64);
+        assert_eq!(
+            report.payload_bytes + report.overhead_bytes + report.slack_bytes,
+            account_bytes as u64
+        );
+        assert_eq!(
+            report.efficiency_bps as u64,
+            payload * 10_000 / account_bytes as u64
+        );
+    }
+
+    #[test]
+    fn an_empty_registry_is_all_overhead_and_slack() {
+        let registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        let report = StorageEfficiency::of(&registry, MemoryRegistry::space_for(50));
+        assert_eq!(report.payload_bytes, 0);
+        assert_eq!(report.overhead_bytes, MemoryRegistry::BASE_SIZE as u64);
+        assert_eq!(report.efficiency_bps, 0);
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+use crate::errors::RegistryError;
+
+#[derive(Accounts)]
+pub struct ManageWriteGrant<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<ManageWriteGrant>, grantee: Pubkey) -> Result<()> {
+    require!(grantee != Pubkey::default(), RegistryError::InvalidGrantee);
+
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+
+    let slot = registry
+        .write_grants
+        .iter_mut()
+        .find(|slot| **slot == Pubkey::default())
+        .ok_or(RegistryError::WriteGrantsFull)?;
+    *slot = grantee;
+    registry.bump_nonce();
+
+    msg!("Write grant issued: {}", grantee);
+    Ok(())
+}
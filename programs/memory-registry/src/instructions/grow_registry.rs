@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::account_size::grow_account;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+use crate::errors::RegistryError;
+
+#[derive(Accounts)]
+pub struct GrowRegistry<'info> {
+    /// CHECK: Only resized, never deserialized, so growing a registry large
+    /// enough to commit into doesn't itself need room to deserialize it.
+    /// Address and owner constrained.
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub registry: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<GrowRegistry>, slots: u32) -> Result<bool> {
+    // Room past `MAX_REGISTRY_ENTRIES` could never be filled.
+    require!(
+        slots as usize <= MemoryRegistry::MAX_REGISTRY_ENTRIES,
+        RegistryError::RegistryFull
+    );
+    let target = MemoryRegistry::space_for(slots as usize);
+    let new_len = grow_account(
+        &ctx.accounts.registry.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        target,
+    )?;
+
+    msg!("Registry grown: {} of {} bytes", new_len, target);
+    Ok(new_len >= target)
+}
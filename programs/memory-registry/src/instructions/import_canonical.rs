@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::{MemoryRegistry, ReplaceStaging};
+use crate::errors::RegistryError;
+use super::export_canonical::{CanonicalExport, CANONICAL_FORMAT_VERSION};
+
+#[derive(Accounts)]
+#[instruction(export: CanonicalExport)]
+pub struct ImportCanonical<'info> {
+    #[account(
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    #[account(
+        mut,
+        seeds = [b"replace-staging", registry.key().as_ref()],
+        bump = staging.bump,
+        has_one = registry,
+        realloc = ReplaceStaging::space_for(staging.entries.len() + export.entries.len()),
+        realloc::payer = authority,
+        realloc::zero = false,
+    )]
+    pub staging: Account<'info, ReplaceStaging>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<ImportCanonical>, export: CanonicalExport) -> Result<()> {
+    ctx.accounts.registry.require_live()?;
+    check_export_format(&export)?;
+
+    // As with `stage_replace_entries`, entry validation happens at commit.
+    let staging = &mut ctx.accounts.staging;
+    staging.entries.extend(export.entries);
+
+    msg!("Imported: {} entries staged", staging.entries.len());
+    Ok(())
+}
+
+/// Fail with `UnsupportedExportVersion` unless `export` was written in this
+/// program's format.
+pub fn check_export_format(export: &CanonicalExport) -> Result<()> {
+    require!(
+        export.format_version == CANONICAL_FORMAT_VERSION
+            && export.entry_size as usize == MemoryRegistry::ENTRY_SIZE,
+        RegistryError::UnsupportedExportVersion
+    );
+    Ok(())
+}
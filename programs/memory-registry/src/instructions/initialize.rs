@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
 use crate::state::MemoryRegistry;
 
 #[derive(Accounts)]
@@ -7,7 +8,7 @@ pub struct Initialize<'info> {
         init,
         payer = authority,
         space = MemoryRegistry::space_for(MemoryRegistry::INITIAL_CAPACITY),
-        seeds = [b"memory-registry", authority.key().as_ref()],
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
         bump,
     )]
     pub registry: Account<'info, MemoryRegistry>,
@@ -16,11 +17,17 @@ pub struct Initialize<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<Initialize>) -> Result<()> {
-    let registry = &mut ctx.accounts.registry;
-    registry.authority = ctx.accounts.authority.key();
-    registry.memory_count = 0;
-    registry.bump = ctx.bumps.registry;
-    registry.entries = Vec::with_capacity(MemoryRegistry::INITIAL_CAPACITY);
+pub(crate) fn handler(ctx: Context<Initialize>) -> Result<()> {
+    init_registry(ctx, 0)
+}
+
+/// Populate a freshly created registry. Shared with `initialize_with_expiry`;
+/// `expires_at` is the registry lifetime (0 = never).
+pub(crate) fn init_registry(ctx: Context<Initialize>, expires_at: i64) -> Result<()> {
+    ctx.accounts.registry.set_inner(MemoryRegistry::new(
+        ctx.accounts.authority.key(),
+        ctx.bumps.registry,
+        expires_at,
+    ));
     Ok(())
 }
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use crate::errors::RegistryError;
+use super::initialize::{init_registry, Initialize};
+
+pub(crate) fn handler(ctx: Context<Initialize>, expires_at: i64) -> Result<()> {
+    let now = unix_now()?;
+    require!(expires_at == 0 || expires_at > now, RegistryError::InvalidExpiry);
+
+    init_registry(ctx, expires_at)?;
+    msg!("Registry initialized: expires_at={}", expires_at);
+    Ok(())
+}
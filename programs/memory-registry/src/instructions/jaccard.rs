@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use std::cmp::Ordering;
+use crate::raw_registry::RawRegistry;
+use crate::errors::RegistryError;
+
+#[derive(Accounts)]
+pub struct Jaccard<'info> {
+    /// CHECK: Any registry of this program, parsed by hand via `RawRegistry`
+    /// so neither entries Vec is copied onto the heap. Owner constrained; the
+    /// discriminator and schema version are checked in the handler.
+    #[account(owner = crate::ID)]
+    pub registry_a: UncheckedAccount<'info>,
+    /// CHECK: As `registry_a`.
+    #[account(owner = crate::ID)]
+    pub registry_b: UncheckedAccount<'info>,
+}
+
+/// Overlap of two registries' content-hash sets, returned via return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct JaccardResult {
+    pub intersection: u32,
+    pub union: u32,
+    /// `intersection / union` in basis points (0-10_000); 0 when both are empty.
+    pub similarity_bps: u16,
+}
+
+/// Combined entry count a single `jaccard` call will scan. Both registries
+/// are read in place, so heap use is constant; the bound is compute: one
+/// merge step per entry, each a `sorted_index` read and a 32-byte compare,
+/// keeps the call well inside the 1.4M CU transaction limit.
+pub const MAX_JACCARD_ENTRIES: usize = 4_000;
+
+pub(crate) fn handler(ctx: Context<Jaccard>) -> Result<JaccardResult> {
+    let data_a = ctx.accounts.registry_a.try_borrow_data()?;
+    let data_b = ctx.accounts.registry_b.try_borrow_data()?;
+    let a = RawRegistry::parse(&data_a)?;
+    let b = RawRegistry::parse(&data_b)?;
+    a.require_live()?;
+    b.require_live()?;
+    require!(
+        a.entry_count() + b.entry_count() <= MAX_JACCARD_ENTRIES,
+        RegistryError::ScanTooLarge
+    );
+    overlap(&a, &b)
+}
+
+/// Both `sorted_index`es are hash-ordered, so a single merge pass counts the
+/// intersection. Pending reservations (zero hash) don't count.
+pub fn overlap(a: &RawRegistry, b: &RawRegistry) -> Result<JaccardResult> {
+    let (mut pos_a, mut pos_b) = (0, 0);
+    let mut next_a = next_live(a, &mut pos_a)?;
+    let mut next_b = next_live(b, &mut pos_b)?;
+    let (mut intersection, mut union) = (0u32, 0u32);
+    loop {
+        let step = match (next_a, next_b) {
+            (Some(x), Some(y)) => a.content_hash(x).cmp(b.content_hash(y)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => break,
+        };
+        union += 1;
+        if step == Ordering::Equal {
+            intersection += 1;
+        }
+        if step != Ordering::Greater {
+            next_a = next_live(a, &mut pos_a)?;
+        }
+        if step != Ordering::Less {
+            next_b = next_live(b, &mut pos_b)?;
+        }
+    }
+
+    let similarity_bps = if union == 0 {
+        0
+    } else {
+        (intersection as u64 * 10_000 / union as u64) as u16
+    };
+    Ok(JaccardResult {
+        intersection,
+        union,
+        similarity_bps,
+    })
+}
+
+/// The next live entry at or after `sorted_index` position `pos`,
+/// advancing `pos` past it.
+fn next_live(registry: &RawRegistry, pos: &mut usize) -> Result<Option<usize>> {
+    while *pos < registry.entry_count() {
+        let slot = registry.sorted_slot(*pos)?;
+        *pos += 1;
+        if registry.is_live(slot) {
+            return Ok(Some(slot));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{MemoryEntry, MemoryRegistry};
+
+    fn registry_bytes(hashes: &[u8], pending: usize) -> Vec<u8> {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        for &h in hashes {
+            registry.push_entry(MemoryEntry {
+                content_hash: [h; 32],
+                ..Default::default()
+            });
+        }
+        for _ in 0..pending {
+            let mut entry = MemoryEntry::default();
+            entry.set_flag(MemoryEntry::FLAG_PENDING, true);
+            registry.push_entry(entry);
+        }
+        let mut data = Vec::new();
+        registry.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn jaccard_of(a: &[u8], b: &[u8], pending: usize) -> JaccardResult {
+        let (data_a, data_b) = (registry_bytes(a, pending), registry_bytes(b, 0));
+        overlap(
+            &RawRegistry::parse(&data_a).unwrap(),
+            &RawRegistry::parse(&data_b).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn partially_overlapping_registries() {
+        // {1, 2, 3, 4} and {3, 4, 5}: 2 shared out of 5.
+        let result = jaccard_of(&[4, 1, 3, 2], &[5, 3, 4], 0);
+        assert_eq!(
+            result,
+            JaccardResult {
+                intersection: 2,
+                union: 5,
+                similarity_bps: 4_000,
+            }
+        );
+    }
+
+    #[test]
+    fn pending_reservations_do_not_count() {
+        let result = jaccard_of(&[1, 2], &[2], 3);
+        assert_eq!((result.intersection, result.union), (1, 2));
+        assert_eq!(result.similarity_bps, 5_000);
+    }
+
+    #[test]
+    fn quarantined_entries_do_not_count() {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        for h in [1, 2] {
+            registry.push_entry(MemoryEntry {
+                content_hash: [h; 32],
+                ..Default::default()
+            });
+        }
+        registry.entries[1].set_flag(MemoryEntry::FLAG_QUARANTINED, true);
+        let mut data_a = Vec::new();
+        registry.try_serialize(&mut data_a).unwrap();
+        let data_b = registry_bytes(&[1, 2], 0);
+
+        let result = overlap(
+            &RawRegistry::parse(&data_a).unwrap(),
+            &RawRegistry::parse(&data_b).unwrap(),
+        )
+        .unwrap();
+        assert_eq!((result.intersection, result.union), (1, 2));
+    }
+
+    #[test]
+    fn identical_disjoint_and_empty() {
+        assert_eq!(jaccard_of(&[1, 2, 3], &[3, 2, 1], 0).similarity_bps, 10_000);
+        assert_eq!(jaccard_of(&[1, 2], &[3], 0).similarity_bps, 0);
+        let empty = jaccard_of(&[], &[], 0);
+        assert_eq!((empty.union, empty.similarity_bps), (0, 0));
+    }
+}
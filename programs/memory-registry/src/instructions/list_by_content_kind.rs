@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::state::MemoryEntry;
+use crate::errors::RegistryError;
+use super::read_registry::ReadRegistry;
+
+pub(crate) fn handler(
+    ctx: Context<ReadRegistry>,
+    content_kind: u8,
+    start: u32,
+    limit: u32,
+) -> Result<Vec<MemoryEntry>> {
+    require!(
+        content_kind <= MemoryEntry::MAX_CONTENT_KIND,
+        RegistryError::InvalidContentKind
+    );
+
+    ctx.accounts
+        .registry
+        .page_where(start, limit, |e| e.content_kind == content_kind)
+}
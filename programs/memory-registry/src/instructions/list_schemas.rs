@@ -0,0 +1,6 @@
+use anchor_lang::prelude::*;
+use super::read_registry::ReadRegistry;
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>) -> Result<Vec<u16>> {
+    Ok(ctx.accounts.registry.schema_ids().to_vec())
+}
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+use crate::state::MemoryEntry;
+use super::read_registry::ReadRegistry;
+
+pub(crate) fn handler(
+    ctx: Context<ReadRegistry>,
+    content_hash: [u8; 32],
+) -> Result<Option<MemoryEntry>> {
+    let registry = &ctx.accounts.registry;
+    registry.require_live()?;
+
+    // A miss is a normal `None` result, not an error.
+    Ok(registry
+        .find_active(&content_hash)
+        .map(|index| registry.entries[index].clone()))
+}
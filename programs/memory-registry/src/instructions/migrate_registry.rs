@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::migration::{migrate_v0, stored_schema_version};
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+use crate::errors::RegistryError;
+
+#[derive(Accounts)]
+pub struct MigrateRegistry<'info> {
+    /// CHECK: Read and rewritten as raw bytes, since an old layout doesn't
+    /// deserialize as `MemoryRegistry`. Address and owner constrained; the
+    /// seeds tie it to the signing authority.
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub registry: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<MigrateRegistry>) -> Result<bool> {
+    let info = ctx.accounts.registry.to_account_info();
+    let mut data = info.try_borrow_mut_data()?;
+
+    let version =
+        stored_schema_version(&data).ok_or(ErrorCode::AccountDiscriminatorMismatch)?;
+    if version == MemoryRegistry::SCHEMA_VERSION {
+        return Ok(false);
+    }
+    require!(version == 0, RegistryError::UnsupportedSchemaVersion);
+
+    let migrated = migrate_v0(&mut data)?;
+    msg!(
+        "Registry migrated: v0 -> v{} entries={}",
+        MemoryRegistry::SCHEMA_VERSION,
+        migrated
+    );
+    Ok(true)
+}
@@ -1,13 +1,158 @@
+
+pub mod age_histogram;
+pub mod assign_cluster;
+pub mod cancel_replace;
+pub mod cancel_reservation;
+pub mod changed_since;
 pub mod cite_memory;
+pub mod clear_entries;
+pub mod commit_replace;
 pub mod create_pool;
+pub mod decay;
+pub mod dedupe;
+pub mod diversity_score;
+pub mod encryption_stats;
+pub mod expired_fraction;
+pub mod export_canonical;
+pub mod filter;
+pub mod finalize_memory;
+pub mod first_missing;
+pub mod get_account_metrics;
+pub mod get_memories_batch;
+pub mod get_preview;
+pub mod get_registry_info;
+pub mod get_storage_efficiency;
+pub mod list_schemas;
+pub mod grant_single_write;
+pub mod grow_registry;
+pub mod import_canonical;
 pub mod initialize;
+pub mod initialize_with_expiry;
+pub mod jaccard;
+pub mod list_by_cluster;
+pub mod list_by_content_kind;
+pub mod list_by_min_confidence;
+pub mod list_never_expiring;
+pub mod lookup;
+pub mod migrate_registry;
+pub mod mutate_entry;
+pub mod needs_migration;
+pub mod neighbor_by_time;
+pub mod open_replace_staging;
+pub mod prefix_collision_report;
+pub mod provenance_of;
+pub mod prove_ownership;
+pub mod prune_and_compact;
+pub mod quarantine_memory;
+pub mod register_content;
 pub mod register_memory;
+pub mod register_memory_dependent;
+pub mod register_memory_derived;
+pub mod register_memory_timestamped;
+pub mod register_memory_with_grant;
+pub mod register_memory_with_options;
+pub mod read_registry;
+pub mod reap_registry;
+pub mod rehash_memory;
+pub mod release_memory;
+pub mod renew_registry;
+pub mod rent_for_entries;
+pub mod rent_headroom;
+pub mod register_schema;
+pub mod repair_counters;
+pub mod replace_all;
+pub mod reserve_memory;
+pub mod retier_all;
+pub mod revoke_write_grant;
+pub mod seal_epoch;
+pub mod self_check;
+pub mod set_app_data;
+pub mod set_circuit_breaker;
+pub mod set_co_authority;
+pub mod set_decay_policy;
+pub mod set_duplicate_policy;
+pub mod set_max_retained;
+pub mod set_require_encryption;
+pub mod set_time_oracle;
+pub mod sorted_view;
+pub mod stage_replace_entries;
+pub mod set_ring_capacity;
+pub mod set_tier_ttls;
+pub mod set_type_caps;
 pub mod store_memory_in_pool;
+pub mod swap_importance;
+pub mod sweep;
+pub mod sync_hash;
+pub mod trim_capacity;
+pub mod type_time_bounds;
+pub mod unfreeze_registry;
+pub mod update_memory_id;
+pub mod verify_before;
+pub mod verify_content;
 pub mod verify_memory;
+pub mod verify_memory_fast;
+pub mod verify_public;
+pub mod verify_with_age;
+pub mod verify_with_count;
 
+pub use cancel_replace::*;
+pub use cancel_reservation::*;
 pub use cite_memory::*;
+pub use clear_entries::*;
+pub use commit_replace::*;
 pub use create_pool::*;
+pub use decay::*;
+pub use dedupe::*;
+pub use diversity_score::*;
+pub use encryption_stats::*;
+pub use expired_fraction::*;
+pub use export_canonical::*;
+pub use filter::*;
+pub use finalize_memory::*;
+pub use get_account_metrics::*;
+pub use get_memories_batch::*;
+pub use get_registry_info::*;
+pub use get_storage_efficiency::*;
+pub use grant_single_write::*;
+pub use grow_registry::*;
+pub use import_canonical::*;
 pub use initialize::*;
+pub use jaccard::*;
+pub use migrate_registry::*;
+pub use mutate_entry::*;
+pub use needs_migration::*;
+pub use neighbor_by_time::*;
+pub use open_replace_staging::*;
+pub use prefix_collision_report::*;
+pub use prove_ownership::*;
+pub use prune_and_compact::*;
 pub use register_memory::*;
+pub use register_memory_timestamped::*;
+pub use register_memory_with_grant::*;
+pub use register_schema::*;
+pub use read_registry::*;
+pub use reap_registry::*;
+pub use renew_registry::*;
+pub use rent_for_entries::*;
+pub use repair_counters::*;
+pub use replace_all::*;
+pub use retier_all::*;
+pub use seal_epoch::*;
+pub use set_circuit_breaker::*;
+pub use set_co_authority::*;
+pub use set_decay_policy::*;
+pub use set_duplicate_policy::*;
+pub use set_max_retained::*;
+pub use set_require_encryption::*;
+pub use set_time_oracle::*;
+pub use sorted_view::*;
+pub use stage_replace_entries::*;
+pub use set_ring_capacity::*;
+pub use set_tier_ttls::*;
+pub use set_type_caps::*;
 pub use store_memory_in_pool::*;
-pub use verify_memory::*;
+pub use sweep::*;
+pub use trim_capacity::*;
+pub use type_time_bounds::*;
+pub use verify_content::*;
+pub use verify_memory_fast::*;
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+
+/// Accounts for the instructions that edit one existing entry in place
+/// (`rehash_memory`, `swap_importance`, `update_memory_id`, `set_app_data`,
+/// `assign_cluster`, `quarantine_memory`, `release_memory`). None changes the
+/// entry count, so the account is never resized.
+#[derive(Accounts)]
+pub struct MutateEntry<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    pub authority: Signer<'info>,
+}
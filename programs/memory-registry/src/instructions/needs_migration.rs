@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use crate::migration::stored_schema_version;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+
+#[derive(Accounts)]
+pub struct NeedsMigration<'info> {
+    /// CHECK: Read as raw bytes, since an old layout doesn't deserialize as
+    /// `MemoryRegistry`. Address and owner constrained.
+    #[account(
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub registry: UncheckedAccount<'info>,
+    /// CHECK: Authority used for PDA derivation only (read-only pre-flight).
+    pub authority: UncheckedAccount<'info>,
+}
+
+/// Schema pre-flight for a registry, returned via return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MigrationStatus {
+    /// True if `migrate_registry` must run before the registry is written.
+    pub needs_migration: bool,
+    /// Layout version stored in the account; 0 = the original layout.
+    pub stored_version: u32,
+    /// `MemoryRegistry::SCHEMA_VERSION` of this program.
+    pub current_version: u32,
+    /// Versions the account is behind; 0 when current.
+    pub version_gap: u32,
+}
+
+/// Compare the raw account's layout version against this program's.
+/// `None` if `data` is not a registry account.
+pub fn migration_status(data: &[u8]) -> Option<MigrationStatus> {
+    let stored_version = stored_schema_version(data)?;
+    let current_version = MemoryRegistry::SCHEMA_VERSION;
+    let version_gap = current_version.saturating_sub(stored_version);
+    Some(MigrationStatus {
+        needs_migration: version_gap > 0,
+        stored_version,
+        current_version,
+        version_gap,
+    })
+}
+
+pub(crate) fn handler(ctx: Context<NeedsMigration>) -> Result<MigrationStatus> {
+    let data = ctx.accounts.registry.try_borrow_data()?;
+    migration_status(&data).ok_or_else(|| error!(ErrorCode::AccountDiscriminatorMismatch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+    use crate::migration::{V0_BASE_SIZE, V0_ENTRY_SIZE};
+
+    #[test]
+    fn up_to_date_registry_needs_nothing() {
+        let mut data = Vec::new();
+        MemoryRegistry::new(Pubkey::default(), 255, 0)
+            .try_serialize(&mut data)
+            .unwrap();
+        let status = migration_status(&data).unwrap();
+        assert!(!status.needs_migration);
+        assert_eq!(status.stored_version, MemoryRegistry::SCHEMA_VERSION);
+        assert_eq!(status.version_gap, 0);
+    }
+
+    #[test]
+    fn original_layout_registry_needs_migration() {
+        let mut data = MemoryRegistry::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[7; 32]);
+        data.extend_from_slice(&2u64.to_le_bytes());
+        data.push(254);
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.resize(V0_BASE_SIZE + 2 * V0_ENTRY_SIZE, 0);
+
+        let status = migration_status(&data).unwrap();
+        assert!(status.needs_migration);
+        assert_eq!(status.stored_version, 0);
+        assert_eq!(status.current_version, MemoryRegistry::SCHEMA_VERSION);
+        assert_eq!(status.version_gap, MemoryRegistry::SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn foreign_account_has_no_status() {
+        assert_eq!(migration_status(&[0; 64]), None);
+    }
+}
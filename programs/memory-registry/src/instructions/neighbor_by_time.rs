@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::MemoryEntry;
+use crate::errors::RegistryError;
+use super::read_registry::ReadRegistry;
+
+/// `direction` values for `neighbor_by_time`.
+pub const NEIGHBOR_PREVIOUS: u8 = 0;
+pub const NEIGHBOR_NEXT: u8 = 1;
+
+pub(crate) fn handler(
+    ctx: Context<ReadRegistry>,
+    content_hash: [u8; 32],
+    direction: u8,
+) -> Result<Option<MemoryEntry>> {
+    require!(direction <= NEIGHBOR_NEXT, RegistryError::InvalidDirection);
+    let registry = &ctx.accounts.registry;
+    registry.require_live()?;
+    let index = registry
+        .find_active(&content_hash)
+        .ok_or(RegistryError::HashNotFound)?;
+
+    // Timeline order is (timestamp, content_hash), so entries sharing a
+    // timestamp are still stepped through one at a time.
+    let key = |e: &MemoryEntry| (e.timestamp, e.content_hash);
+    let target = key(&registry.entries[index]);
+    let live = registry.entries.iter().filter(|e| e.is_live());
+
+    let neighbor = if direction == NEIGHBOR_PREVIOUS {
+        live.filter(|e| key(e) < target).max_by_key(|e| key(e))
+    } else {
+        live.filter(|e| key(e) > target).min_by_key(|e| key(e))
+    };
+    Ok(neighbor.cloned())
+}
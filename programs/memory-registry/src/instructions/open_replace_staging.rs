@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::{MemoryRegistry, ReplaceStaging};
+
+#[derive(Accounts)]
+pub struct OpenReplaceStaging<'info> {
+    #[account(
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    #[account(
+        init,
+        payer = authority,
+        space = ReplaceStaging::space_for(0),
+        seeds = [b"replace-staging", registry.key().as_ref()],
+        bump,
+    )]
+    pub staging: Account<'info, ReplaceStaging>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<OpenReplaceStaging>) -> Result<()> {
+    ctx.accounts.registry.require_live()?;
+
+    let staging = &mut ctx.accounts.staging;
+    staging.registry = ctx.accounts.registry.key();
+    staging.bump = ctx.bumps.staging;
+    staging.entries = Vec::new();
+    Ok(())
+}
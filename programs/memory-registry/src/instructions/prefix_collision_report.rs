@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::errors::RegistryError;
+use super::read_registry::ReadRegistry;
+
+/// How live entries cluster by content-hash prefix, returned via return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PrefixCollisions {
+    /// Distinct prefixes among live entries.
+    pub prefixes: u32,
+    /// Entries sharing the most common prefix; 0 for an empty registry.
+    pub max_cluster: u32,
+    /// Mean entries per distinct prefix, times 100; 0 for an empty registry.
+    pub average_x100: u32,
+}
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>, prefix_len: u8) -> Result<PrefixCollisions> {
+    require!(
+        (1..=32).contains(&prefix_len),
+        RegistryError::InvalidPrefixLength
+    );
+    let registry = &ctx.accounts.registry;
+    let len = prefix_len as usize;
+
+    // `sorted_index` is hash-ordered, so entries sharing a prefix are
+    // adjacent and one pass counts every cluster.
+    let (mut prefixes, mut max_cluster, mut live) = (0u32, 0u32, 0u32);
+    let mut current: Option<&[u8]> = None;
+    let mut run = 0u32;
+    for &i in registry.sorted_index.iter() {
+        let entry = &registry.entries[i as usize];
+        if !entry.is_live() {
+            continue;
+        }
+        live += 1;
+        let prefix = &entry.content_hash[..len];
+        if current == Some(prefix) {
+            run += 1;
+        } else {
+            prefixes += 1;
+            current = Some(prefix);
+            run = 1;
+        }
+        max_cluster = max_cluster.max(run);
+    }
+
+    let average_x100 = if prefixes == 0 {
+        0
+    } else {
+        (live as u64 * 100 / prefixes as u64) as u32
+    };
+
+    Ok(PrefixCollisions {
+        prefixes,
+        max_cluster,
+        average_x100,
+    })
+}
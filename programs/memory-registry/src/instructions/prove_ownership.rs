@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::pda::REGISTRY_SEED;
+use crate::ed25519::verify_preceding_ed25519;
+use crate::state::MemoryRegistry;
+use crate::errors::RegistryError;
+
+#[derive(Accounts)]
+pub struct ProveOwnership<'info> {
+    #[account(
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    /// CHECK: Authority used for PDA derivation only — ownership is proven by
+    /// the pre-signed challenge, not by this account signing.
+    pub authority: UncheckedAccount<'info>,
+    /// CHECK: Instructions sysvar, used to inspect the preceding Ed25519
+    /// precompile instruction. Address-constrained.
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+/// Attestation returned to the verifier via return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OwnershipProof {
+    /// Registry authority that signed the challenge.
+    pub authority: Pubkey,
+    /// Content hash proven present in the authority's registry.
+    pub content_hash: [u8; 32],
+    /// Registration timestamp of the matching entry.
+    pub timestamp: i64,
+}
+
+pub(crate) fn handler(
+    ctx: Context<ProveOwnership>,
+    content_hash: [u8; 32],
+    challenge: [u8; 32],
+    signature: [u8; 64],
+) -> Result<OwnershipProof> {
+    let registry = &ctx.accounts.registry;
+    registry.require_live()?;
+
+    // The verifier's transaction must carry an Ed25519 precompile instruction
+    // (immediately before this one) checking the authority's signature over
+    // the challenge.
+    require!(
+        verify_preceding_ed25519(
+            &ctx.accounts.instructions.to_account_info(),
+            &registry.authority,
+            &challenge,
+            &signature,
+        ),
+        RegistryError::InvalidOwnershipProof
+    );
+
+    let index = registry
+        .find_active(&content_hash)
+        .ok_or(RegistryError::HashNotFound)?;
+
+    Ok(OwnershipProof {
+        authority: registry.authority,
+        content_hash,
+        timestamp: registry.entries[index].timestamp,
+    })
+}
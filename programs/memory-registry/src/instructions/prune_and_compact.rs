@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::account_size::shrink_registry;
+use crate::scan::WorkBudget;
+use crate::state::MemoryRegistry;
+
+#[derive(Accounts)]
+pub struct PruneAndCompact<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Must sign as well when the registry has a co-authority set.
+    pub co_authority: Option<Signer<'info>>,
+}
+
+/// Outcome of a `prune_and_compact` call, returned via return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PruneResult {
+    /// Entries actually removed (absent hashes are ignored).
+    pub removed: u32,
+    /// Resumption cursor: how many of the supplied hashes were processed.
+    /// Less than `hashes.len()` means the call stopped early; resubmit
+    /// `hashes[processed..]`.
+    pub processed: u32,
+    /// Named entries kept because `require_mutable` refuses them (sealed,
+    /// immutable or quarantined), as `sweep` keeps them.
+    pub skipped: u32,
+}
+
+pub(crate) fn handler(
+    ctx: Context<PruneAndCompact>,
+    hashes: Vec<[u8; 32]>,
+    max_work: u32,
+) -> Result<PruneResult> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    registry.require_co_signed(ctx.accounts.co_authority.as_ref())?;
+
+    // Each removal shifts the tail of `entries` (O(n)), so the work budget
+    // caps how many hashes are processed per call.
+    let mut budget = WorkBudget::new(max_work);
+    let result = prune(registry, &hashes, || budget.tick());
+
+    let remaining = registry.entries.len();
+    let refund = shrink_registry(
+        &ctx.accounts.registry,
+        &ctx.accounts.authority.to_account_info(),
+        remaining,
+    )?;
+
+    msg!(
+        "Pruned and compacted: removed={} skipped={} processed={} remaining={} refund={}",
+        result.removed,
+        result.skipped,
+        result.processed,
+        remaining,
+        refund
+    );
+    Ok(result)
+}
+
+/// Remove each of `hashes` that is present and mutable, in order, while
+/// `tick` allows. An entry `require_mutable` refuses is skipped and counted
+/// rather than failing the batch, matching `sweep` and `dedupe`.
+pub fn prune(
+    registry: &mut MemoryRegistry,
+    hashes: &[[u8; 32]],
+    mut tick: impl FnMut() -> bool,
+) -> PruneResult {
+    let mut result = PruneResult {
+        removed: 0,
+        processed: 0,
+        skipped: 0,
+    };
+    for hash in hashes {
+        if !tick() {
+            break;
+        }
+        if let Some(index) = registry.find_entry(hash) {
+            if registry.require_mutable(index).is_ok() {
+                registry.remove_entry(index);
+                result.removed += 1;
+            } else {
+                result.skipped += 1;
+            }
+        }
+        result.processed += 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MemoryEntry;
+
+    fn registry_of(hashes: &[u8]) -> MemoryRegistry {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        for &h in hashes {
+            registry.push_entry(MemoryEntry {
+                content_hash: [h; 32],
+                ..Default::default()
+            });
+        }
+        registry
+    }
+
+    fn hashes(bytes: &[u8]) -> Vec<[u8; 32]> {
+        bytes.iter().map(|&b| [b; 32]).collect()
+    }
+
+    #[test]
+    fn immutable_and_quarantined_entries_are_skipped_not_fatal() {
+        let mut registry = registry_of(&[1, 2, 3, 4]);
+        registry.entries[1].set_flag(MemoryEntry::FLAG_IMMUTABLE, true);
+        registry.entries[2].set_flag(MemoryEntry::FLAG_QUARANTINED, true);
+
+        let result = prune(&mut registry, &hashes(&[1, 2, 3, 4, 9]), || true);
+        assert_eq!(
+            result,
+            PruneResult {
+                removed: 2,
+                processed: 5,
+                skipped: 2,
+            }
+        );
+        let left: Vec<u8> = registry.entries.iter().map(|e| e.content_hash[0]).collect();
+        assert_eq!(left, vec![2, 3]);
+    }
+
+    #[test]
+    fn sealed_entries_are_skipped() {
+        let mut registry = registry_of(&[1, 2]);
+        registry.current_epoch = 1;
+        registry.push_entry(MemoryEntry {
+            content_hash: [3; 32],
+            epoch: 1,
+            ..Default::default()
+        });
+
+        let result = prune(&mut registry, &hashes(&[1, 3]), || true);
+        assert_eq!((result.removed, result.skipped), (1, 1));
+        assert!(registry.find_entry(&[1; 32]).is_some(), "sealed entry stays");
+        assert!(registry.find_entry(&[3; 32]).is_none());
+    }
+
+    #[test]
+    fn stops_when_the_budget_runs_out() {
+        let mut registry = registry_of(&[1, 2, 3]);
+        let mut units = 2;
+        let result = prune(&mut registry, &hashes(&[1, 2, 3]), || {
+            units -= 1;
+            units >= 0
+        });
+        assert_eq!((result.removed, result.processed), (2, 2));
+        assert_eq!(registry.entries.len(), 1);
+    }
+}
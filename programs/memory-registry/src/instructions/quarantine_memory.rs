@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use crate::state::MemoryEntry;
+use crate::errors::RegistryError;
+use super::mutate_entry::MutateEntry;
+
+pub(crate) fn handler(ctx: Context<MutateEntry>, content_hash: [u8; 32]) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+
+    // Deliberately no `require_mutable`: sealed and immutable entries can be
+    // held for review too. Only the review flag changes.
+    let index = registry
+        .find_entry(&content_hash)
+        .ok_or(RegistryError::HashNotFound)?;
+    registry.entries[index].set_flag(MemoryEntry::FLAG_QUARANTINED, true);
+    registry.touch_entry(index, unix_now()?);
+
+    msg!("Memory quarantined: memory_id={}", registry.entries[index].memory_id);
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+
+/// Accounts for every read-only instruction over a single registry: its PDA
+/// and the authority it is derived from. Nothing is written and nobody signs,
+/// so anyone who knows the authority can read.
+#[derive(Accounts)]
+pub struct ReadRegistry<'info> {
+    #[account(
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    /// CHECK: Only seeds the registry PDA and is matched by `has_one`; never
+    /// read, written or required to sign.
+    pub authority: UncheckedAccount<'info>,
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::clock::unix_now;
+use crate::state::MemoryRegistry;
+use crate::errors::RegistryError;
+
+#[derive(Accounts)]
+pub struct ReapRegistry<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+        close = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    /// CHECK: Original registry authority; only receives the refunded rent.
+    /// It need not sign — reaping an expired registry is permissionless.
+    #[account(mut)]
+    pub authority: UncheckedAccount<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<ReapRegistry>) -> Result<()> {
+    let registry = &ctx.accounts.registry;
+    let now = unix_now()?;
+    require!(registry.is_expired(now), RegistryError::RegistryNotExpired);
+
+    // A replace-staging account left open is not closed here; the authority
+    // has to `cancel_replace` it before the registry expires.
+    msg!(
+        "Registry reaped: authority={} entries={}",
+        registry.authority,
+        registry.entries.len()
+    );
+    Ok(())
+}
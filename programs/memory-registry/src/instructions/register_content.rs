@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use super::register_memory::{insert_memory, RegisterMemory, RegisterOptions};
+use super::verify_content::content_hash;
+
+pub(crate) fn handler(
+    ctx: Context<RegisterMemory>,
+    content: Vec<u8>,
+    memory_type: u8,
+    importance_tier: u8,
+    memory_id: u64,
+    encrypted: bool,
+) -> Result<()> {
+    // Same hash and bound as `verify_content`, so anything registered this
+    // way can be verified the same way. This instruction carries more
+    // accounts and arguments, so the packet limit already keeps its content
+    // shorter than the bound.
+    let content_hash = content_hash(&content)?;
+
+    insert_memory(
+        &mut ctx.accounts.registry,
+        content_hash,
+        memory_type,
+        importance_tier,
+        memory_id,
+        encrypted,
+        &RegisterOptions::default(),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::register_memory::apply_registration;
+    use crate::state::MemoryRegistry;
+
+    #[test]
+    fn stores_the_sha256_of_the_content() {
+        // FIPS 180-2 test vector: SHA-256("abc").
+        let abc: [u8; 32] = [
+            0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae,
+            0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61,
+            0xf2, 0x00, 0x15, 0xad,
+        ];
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        let options = RegisterOptions::default();
+        let hash = content_hash(b"abc").unwrap();
+        apply_registration(&mut registry, hash, 0, 1, 1, false, &options, None, 0).unwrap();
+
+        assert_eq!(registry.entries[0].content_hash, abc);
+        assert_eq!(registry.find_active(&abc), Some(0));
+    }
+}
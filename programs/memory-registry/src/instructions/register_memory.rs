@@ -1,22 +1,20 @@
 use anchor_lang::prelude::*;
+use crate::account_size::require_rent_exempt;
+use crate::pda::REGISTRY_SEED;
+use crate::clock::unix_now;
 use crate::state::{MemoryEntry, MemoryRegistry};
+use crate::error_context::fail_with_context;
 use crate::errors::RegistryError;
+use crate::events::{CircuitBreakerTripped, MemoryRegistered, RegisterIntent};
 
 #[derive(Accounts)]
 pub struct RegisterMemory<'info> {
     #[account(
         mut,
-        seeds = [b"memory-registry", authority.key().as_ref()],
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
         bump = registry.bump,
         has_one = authority,
-        realloc = MemoryRegistry::space_for(
-            registry.entries.len() + 1
-                + if registry.entries.len() + 1 > registry.entries.capacity() {
-                    MemoryRegistry::REALLOC_INCREMENT
-                } else {
-                    0
-                }
-        ),
+        realloc = registry.grow_target(registry.to_account_info().data_len()),
         realloc::payer = authority,
         realloc::zero = false,
     )]
@@ -26,7 +24,7 @@ pub struct RegisterMemory<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<RegisterMemory>,
     content_hash: [u8; 32],
     memory_type: u8,
@@ -34,30 +32,389 @@ pub fn handler(
     memory_id: u64,
     encrypted: bool,
 ) -> Result<()> {
-    require!(memory_type <= 3, RegistryError::InvalidMemoryType);
+    insert_memory(
+        &mut ctx.accounts.registry,
+        content_hash,
+        memory_type,
+        importance_tier,
+        memory_id,
+        encrypted,
+        &RegisterOptions::default(),
+    )?;
+    Ok(())
+}
 
-    let registry = &mut ctx.accounts.registry;
+/// Optional per-entry settings accepted by `register_memory_with_options`.
+/// The defaults reproduce plain `register_memory`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct RegisterOptions {
+    /// Explicit expiry: `None` applies the tier's default TTL, `Some(0)`
+    /// never expires, `Some(ts)` must be in the future.
+    pub expires_at: Option<i64>,
+    /// Content schema id; 0 = unstructured. Non-zero ids must have been
+    /// registered via `register_schema`.
+    pub schema_id: u16,
+    /// Hash of a redacted, shareable snippet; all zeros = no preview.
+    pub preview_hash: [u8; 32],
+    /// Client correlation id echoed in the `RegisterIntent` and
+    /// `MemoryRegistered` events; 0 = none.
+    pub request_id: u64,
+    /// Confidence in the memory, 0-100; defaults to 0.
+    pub confidence: u8,
+    /// Make the entry write-once (see `MemoryEntry::FLAG_IMMUTABLE`).
+    pub immutable: bool,
+    /// Publish the entry to `verify_public` (see `MemoryEntry::FLAG_PUBLIC`).
+    pub public: bool,
+    /// AES-GCM nonce/IV for encrypted content; all zeros = none. Rejected
+    /// with `NonceWithoutEncryption` unless `encrypted` is set.
+    pub encryption_nonce: [u8; 12],
+    /// Opaque application bytes (see `MemoryEntry::app_data`); all zeros = none.
+    pub app_data: [u8; 32],
+    /// One of the `MemoryEntry::CONTENT_*` kinds; defaults to text.
+    pub content_kind: u8,
+    /// Embedding cluster (see `MemoryEntry::cluster_id`); 0 = unassigned.
+    pub cluster_id: u16,
+    /// `memory_id`s of source memories (see `MemoryEntry::derived_from`).
+    /// Each must belong to a registered entry, or the call fails with
+    /// `SourceMissing`.
+    pub derived_from: Vec<u64>,
+}
 
-    // Check for duplicate hash
-    for entry in &registry.entries {
-        if entry.content_hash == content_hash {
-            return Err(RegistryError::DuplicateHash.into());
-        }
-    }
+/// Validate and append a new entry, applying the registry's duplicate policy
+/// if the hash is already present. Shared by every register variant so the
+/// duplicate/cap checks can't drift between them. Brackets the work with the
+/// `RegisterIntent` / `MemoryRegistered` events. Returns whether anything
+/// was written: false for a duplicate under `DUPLICATE_IGNORE`.
+pub(crate) fn insert_memory(
+    registry: &mut Account<MemoryRegistry>,
+    content_hash: [u8; 32],
+    memory_type: u8,
+    importance_tier: u8,
+    memory_id: u64,
+    encrypted: bool,
+    options: &RegisterOptions,
+) -> Result<bool> {
+    insert_memory_at(
+        registry,
+        content_hash,
+        memory_type,
+        importance_tier,
+        memory_id,
+        encrypted,
+        options,
+        None,
+    )
+}
+
+/// `insert_memory` with an optional externally attested `timestamp` to store
+/// instead of the clock time. Only callers that have verified the timestamp
+/// (see `register_memory_timestamped`) may pass one; it is deliberately not
+/// part of the client-supplied `RegisterOptions`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn insert_memory_at(
+    registry: &mut Account<MemoryRegistry>,
+    content_hash: [u8; 32],
+    memory_type: u8,
+    importance_tier: u8,
+    memory_id: u64,
+    encrypted: bool,
+    options: &RegisterOptions,
+    timestamp: Option<i64>,
+) -> Result<bool> {
+    registry.require_live()?;
+    registry.require_unfrozen()?;
+    require_rent_exempt(&registry.to_account_info())?;
 
-    let clock = Clock::get()?;
+    emit!(RegisterIntent {
+        request_id: options.request_id,
+        content_hash,
+    });
 
-    registry.entries.push(MemoryEntry {
+    let now = unix_now()?;
+    let written = apply_registration(
+        registry,
         content_hash,
-        timestamp: clock.unix_timestamp,
         memory_type,
         importance_tier,
         memory_id,
         encrypted,
-        _padding: [0; 3],
+        options,
+        timestamp,
+        now,
+    )?;
+
+    emit!(MemoryRegistered {
+        request_id: options.request_id,
+        content_hash,
+        memory_id,
+        written,
     });
+    if written {
+        trip_circuit_breaker(registry, now);
+    }
+    Ok(written)
+}
+
+/// Count a registration against the circuit breaker, emitting
+/// `CircuitBreakerTripped` if it froze the registry. The registration that
+/// trips the breaker still lands: failing it would roll back the freeze too.
+pub(crate) fn trip_circuit_breaker(registry: &mut MemoryRegistry, now: i64) {
+    if registry.record_registration(now) {
+        emit!(CircuitBreakerTripped {
+            burst_count: registry.burst_count,
+            burst_window_start: registry.burst_window_start,
+            tripped_at: now,
+        });
+    }
+}
+
+/// Body of `insert_memory` at clock time `now`. Returns whether anything was
+/// written (false for a duplicate under `DUPLICATE_IGNORE`).
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn apply_registration(
+    registry: &mut MemoryRegistry,
+    content_hash: [u8; 32],
+    memory_type: u8,
+    importance_tier: u8,
+    memory_id: u64,
+    encrypted: bool,
+    options: &RegisterOptions,
+    timestamp: Option<i64>,
+    now: i64,
+) -> Result<bool> {
+    check_new_entry(registry, &content_hash, memory_type, importance_tier, encrypted)?;
+    require!(
+        options.confidence <= MemoryEntry::MAX_CONFIDENCE,
+        RegistryError::InvalidConfidence
+    );
+    require!(
+        options.schema_id == 0 || registry.has_schema(options.schema_id),
+        RegistryError::UnknownSchema
+    );
+    require!(
+        encrypted || options.encryption_nonce == [0; 12],
+        RegistryError::NonceWithoutEncryption
+    );
+    require!(
+        options.content_kind <= MemoryEntry::MAX_CONTENT_KIND,
+        RegistryError::InvalidContentKind
+    );
+    require!(
+        options.derived_from.len() <= MemoryEntry::MAX_DERIVED_FROM,
+        RegistryError::TooManySources
+    );
+    for source in &options.derived_from {
+        require!(
+            registry
+                .entries
+                .iter()
+                .any(|entry| !entry.is_pending() && entry.memory_id == *source),
+            RegistryError::SourceMissing
+        );
+    }
+    let mut derived_from = [0u64; MemoryEntry::MAX_DERIVED_FROM];
+    derived_from[..options.derived_from.len()].copy_from_slice(&options.derived_from);
+    let derived_count = options.derived_from.len() as u8;
+
+    let existing = registry.find_entry(&content_hash);
 
-    registry.memory_count = registry.entries.len() as u64;
+    // Duplicate hash: behaviour depends on the registry's duplicate policy
+    if existing.is_some() {
+        match registry.duplicate_policy {
+            MemoryRegistry::DUPLICATE_IGNORE => return Ok(false),
+            MemoryRegistry::DUPLICATE_UPDATE => {}
+            _ => return Err(RegistryError::DuplicateHash.into()),
+        }
+    }
+
+    let existing = check_insert_policy(registry, &content_hash, memory_type, existing)?;
+
+    // `last_modified` and the expiry check always use the clock; only the
+    // stored `timestamp` (and the TTL counted from it) may be attested.
+    let timestamp = timestamp.unwrap_or(now);
+
+    let expires_at = match options.expires_at {
+        Some(ts) => {
+            require!(ts == 0 || ts > now, RegistryError::InvalidExpiry);
+            ts
+        }
+        None => registry.default_expiry(importance_tier, timestamp),
+    };
 
+    if let Some(index) = existing {
+        registry.require_mutable(index)?;
+        registry.set_memory_type(index, memory_type);
+        registry.set_importance_tier(index, importance_tier);
+        let entry = &mut registry.entries[index];
+        entry.timestamp = timestamp;
+        entry.expires_at = expires_at;
+        entry.schema_id = options.schema_id;
+        entry.preview_hash = options.preview_hash;
+        entry.confidence = options.confidence;
+        entry.set_flag(MemoryEntry::FLAG_IMMUTABLE, options.immutable);
+        entry.set_flag(MemoryEntry::FLAG_PUBLIC, options.public);
+        entry.memory_id = memory_id;
+        registry.set_encrypted(index, encrypted);
+        registry.entries[index].encryption_nonce = options.encryption_nonce;
+        registry.entries[index].app_data = options.app_data;
+        registry.entries[index].content_kind = options.content_kind;
+        registry.entries[index].cluster_id = options.cluster_id;
+        registry.entries[index].derived_from = derived_from;
+        registry.entries[index].derived_count = derived_count;
+        registry.touch_entry(index, now);
+        return Ok(true);
+    }
+
+    let mut entry = MemoryEntry {
+        content_hash,
+        timestamp,
+        memory_type,
+        importance_tier,
+        memory_id,
+        flags: 0,
+        revision: 0,
+        expires_at,
+        schema_id: options.schema_id,
+        preview_hash: options.preview_hash,
+        epoch: registry.current_epoch,
+        confidence: options.confidence,
+        decay_steps: 0,
+        encryption_nonce: options.encryption_nonce,
+        app_data: options.app_data,
+        content_kind: options.content_kind,
+        cluster_id: options.cluster_id,
+        last_modified: now,
+        derived_from,
+        derived_count,
+    };
+    entry.set_flag(MemoryEntry::FLAG_ENCRYPTED, encrypted);
+    entry.set_flag(MemoryEntry::FLAG_IMMUTABLE, options.immutable);
+    entry.set_flag(MemoryEntry::FLAG_PUBLIC, options.public);
+    registry.require_room()?;
+    registry.push_entry(entry);
+
+    Ok(true)
+}
+
+/// Field checks every new live entry must pass, whichever path creates it
+/// (`insert_memory`, `finalize_memory`): a valid type and tier, a real hash
+/// (the zero hash marks pending reservations), and the registry's
+/// encryption requirement.
+pub(crate) fn check_new_entry(
+    registry: &MemoryRegistry,
+    content_hash: &[u8; 32],
+    memory_type: u8,
+    importance_tier: u8,
+    encrypted: bool,
+) -> Result<()> {
+    require!(memory_type <= 3, RegistryError::InvalidMemoryType);
+    require!(importance_tier <= 2, RegistryError::InvalidImportanceTier);
+    require!(*content_hash != [0; 32], RegistryError::InvalidContentHash);
+    require!(
+        encrypted || !registry.require_encryption,
+        RegistryError::EncryptionRequired
+    );
     Ok(())
 }
+
+/// Apply the registry's limits to a write of `content_hash` as a live entry
+/// of `memory_type`, where `existing` is the entry it updates, if any: evict
+/// to make room in the episodic ring and under `max_retained`, then enforce
+/// the per-type cap. Every path that adds or retypes a live entry goes
+/// through here; `replace_entries_at` checks the same limits on the whole
+/// set. Evictions shift indices, so returns `existing` re-found.
+pub(crate) fn check_insert_policy(
+    registry: &mut MemoryRegistry,
+    content_hash: &[u8; 32],
+    memory_type: u8,
+    existing: Option<usize>,
+) -> Result<Option<usize>> {
+    // An update that keeps the entry's type takes no new slot of that type,
+    // so it is exempt from the ring and the cap.
+    let type_index = memory_type as usize;
+    let takes_type_slot =
+        existing.is_none_or(|index| registry.entries[index].memory_type != memory_type);
+
+    let mut existing = existing;
+    if takes_type_slot && memory_type == 0 {
+        registry.make_ring_room()?;
+        existing = registry.find_entry(content_hash);
+    }
+
+    // Retention limit: only a new entry adds to the stored count.
+    let max_retained = registry.max_retained;
+    if existing.is_none() && max_retained != 0 {
+        registry.evict_oldest(max_retained - 1);
+        require!(
+            registry.stored_count() < max_retained,
+            RegistryError::RetentionFull
+        );
+    }
+
+    // Per-type cap (0 = unlimited).
+    let cap = registry.type_caps[type_index];
+    if takes_type_slot && cap != 0 && registry.type_counts[type_index] >= cap {
+        return Err(fail_with_context(
+            RegistryError::TypeCapExceeded,
+            memory_type as u64,
+            registry.type_counts[type_index] as u64,
+            cap as u64,
+        ));
+    }
+    Ok(existing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignored_duplicate_writes_nothing() {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        registry.push_entry(MemoryEntry {
+            content_hash: [7; 32],
+            memory_id: 1,
+            ..Default::default()
+        });
+        registry.duplicate_policy = MemoryRegistry::DUPLICATE_IGNORE;
+        let nonce = registry.nonce;
+
+        let written = apply_registration(
+            &mut registry,
+            [7; 32],
+            1,
+            2,
+            99,
+            false,
+            &RegisterOptions::default(),
+            None,
+            0,
+        )
+        .unwrap();
+        assert!(!written);
+        assert_eq!(registry.nonce, nonce);
+        assert_eq!(registry.entries.len(), 1);
+        assert_eq!(registry.entries[0].memory_id, 1);
+    }
+
+    #[test]
+    fn rejected_duplicate_fails() {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        registry.push_entry(MemoryEntry {
+            content_hash: [7; 32],
+            ..Default::default()
+        });
+        let result = apply_registration(
+            &mut registry,
+            [7; 32],
+            0,
+            0,
+            0,
+            false,
+            &RegisterOptions::default(),
+            None,
+            0,
+        );
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use crate::errors::RegistryError;
+use crate::state::MemoryRegistry;
+use super::register_memory::{insert_memory, RegisterMemory, RegisterOptions};
+
+pub(crate) fn handler(
+    ctx: Context<RegisterMemory>,
+    content_hash: [u8; 32],
+    memory_type: u8,
+    importance_tier: u8,
+    memory_id: u64,
+    encrypted: bool,
+    requires_hash: [u8; 32],
+) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    require_dependency(registry, &requires_hash)?;
+
+    insert_memory(
+        registry,
+        content_hash,
+        memory_type,
+        importance_tier,
+        memory_id,
+        encrypted,
+        &RegisterOptions::default(),
+    )?;
+    Ok(())
+}
+
+/// Referential integrity: the parent must already be registered and live,
+/// so a pending reservation or a quarantined entry doesn't count.
+pub fn require_dependency(registry: &MemoryRegistry, requires_hash: &[u8; 32]) -> Result<()> {
+    require!(
+        registry.find_active(requires_hash).is_some(),
+        RegistryError::DependencyMissing
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::register_memory::apply_registration;
+    use crate::state::MemoryEntry;
+
+    const NOW: i64 = 1_000;
+
+    fn register(registry: &mut MemoryRegistry, hash: u8, memory_id: u64) {
+        let options = RegisterOptions::default();
+        let written =
+            apply_registration(registry, [hash; 32], 0, 1, memory_id, false, &options, None, NOW)
+                .unwrap();
+        assert!(written);
+    }
+
+    #[test]
+    fn dependent_waits_for_a_live_parent() {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        let err = require_dependency(&registry, &[1; 32]).unwrap_err();
+        assert_eq!(err, RegistryError::DependencyMissing.into());
+
+        register(&mut registry, 1, 10);
+        registry.entries[0].set_flag(MemoryEntry::FLAG_QUARANTINED, true);
+        let err = require_dependency(&registry, &[1; 32]).unwrap_err();
+        assert_eq!(err, RegistryError::DependencyMissing.into());
+
+        registry.entries[0].set_flag(MemoryEntry::FLAG_QUARANTINED, false);
+        require_dependency(&registry, &[1; 32]).unwrap();
+        register(&mut registry, 2, 11);
+        assert_eq!(registry.find_active(&[2; 32]), Some(1));
+        assert_eq!(registry.entries[1].timestamp, NOW);
+    }
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use super::register_memory::{insert_memory, RegisterMemory, RegisterOptions};
+
+pub(crate) fn handler(
+    ctx: Context<RegisterMemory>,
+    content_hash: [u8; 32],
+    memory_type: u8,
+    importance_tier: u8,
+    memory_id: u64,
+    encrypted: bool,
+    derived_from: Vec<u64>,
+) -> Result<()> {
+    // Source existence and the source count are checked with the rest of
+    // the registration, in `insert_memory`.
+    insert_memory(
+        &mut ctx.accounts.registry,
+        content_hash,
+        memory_type,
+        importance_tier,
+        memory_id,
+        encrypted,
+        &RegisterOptions {
+            derived_from,
+            ..Default::default()
+        },
+    )?;
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::clock::unix_now;
+use crate::ed25519::verify_preceding_ed25519;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+use crate::errors::RegistryError;
+use super::register_memory::{insert_memory_at, RegisterOptions};
+
+#[derive(Accounts)]
+pub struct RegisterMemoryTimestamped<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+        realloc = registry.grow_target(registry.to_account_info().data_len()),
+        realloc::payer = authority,
+        realloc::zero = false,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: Instructions sysvar, used to inspect the preceding Ed25519
+    /// precompile instruction. Address-constrained.
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// A timestamp and the time oracle's signature over
+/// `timestamp_message(content_hash, timestamp)`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TimestampAttestation {
+    pub timestamp: i64,
+    pub signature: [u8; 64],
+}
+
+/// How far past the cluster clock an attested timestamp may be, in seconds:
+/// room for clock drift between the oracle and the cluster, not enough for a
+/// postdated attestation to pass as recent in `verify_with_age`.
+pub const MAX_TIMESTAMP_SKEW: i64 = 60;
+
+/// Fail with `TimestampInFuture` if `timestamp` is more than
+/// `MAX_TIMESTAMP_SKEW` seconds after `now`.
+pub fn check_attested_timestamp(timestamp: i64, now: i64) -> Result<()> {
+    require!(
+        timestamp <= now.saturating_add(MAX_TIMESTAMP_SKEW),
+        RegistryError::TimestampInFuture
+    );
+    Ok(())
+}
+
+/// Message the time oracle signs: `content_hash || timestamp` (i64 LE).
+/// Binding the hash stops one signed timestamp being replayed onto other
+/// content.
+pub fn timestamp_message(content_hash: &[u8; 32], timestamp: i64) -> [u8; 40] {
+    let mut message = [0u8; 40];
+    message[..32].copy_from_slice(content_hash);
+    message[32..].copy_from_slice(&timestamp.to_le_bytes());
+    message
+}
+
+pub(crate) fn handler(
+    ctx: Context<RegisterMemoryTimestamped>,
+    content_hash: [u8; 32],
+    memory_type: u8,
+    importance_tier: u8,
+    memory_id: u64,
+    encrypted: bool,
+    attestation: TimestampAttestation,
+) -> Result<()> {
+    let oracle = ctx.accounts.registry.time_oracle;
+    require!(
+        oracle != Pubkey::default()
+            && verify_preceding_ed25519(
+                &ctx.accounts.instructions.to_account_info(),
+                &oracle,
+                &timestamp_message(&content_hash, attestation.timestamp),
+                &attestation.signature,
+            ),
+        RegistryError::InvalidTimestampSignature
+    );
+    check_attested_timestamp(attestation.timestamp, unix_now()?)?;
+
+    insert_memory_at(
+        &mut ctx.accounts.registry,
+        content_hash,
+        memory_type,
+        importance_tier,
+        memory_id,
+        encrypted,
+        &RegisterOptions::default(),
+        Some(attestation.timestamp),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamp_message_is_hash_then_le_timestamp() {
+        let hash = [0xAB; 32];
+        let message = timestamp_message(&hash, 0x0102_0304_0506_0708);
+        assert_eq!(message[..32], hash);
+        assert_eq!(message[32..], [8, 7, 6, 5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn attested_timestamps_may_lead_the_clock_by_the_skew_only() {
+        let now = 1_700_000_000;
+        check_attested_timestamp(now - 86_400, now).unwrap();
+        check_attested_timestamp(now + MAX_TIMESTAMP_SKEW, now).unwrap();
+        let err = check_attested_timestamp(now + MAX_TIMESTAMP_SKEW + 1, now).unwrap_err();
+        assert_eq!(err, RegistryError::TimestampInFuture.into());
+    }
+
+    #[test]
+    fn timestamp_message_binds_both_inputs() {
+        let message = timestamp_message(&[1; 32], 1_700_000_000);
+        assert_ne!(message, timestamp_message(&[2; 32], 1_700_000_000));
+        assert_ne!(message, timestamp_message(&[1; 32], 1_700_000_001));
+        assert_ne!(timestamp_message(&[0; 32], -1), timestamp_message(&[0; 32], 0));
+    }
+}
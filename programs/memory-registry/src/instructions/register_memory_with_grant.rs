@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+use crate::errors::RegistryError;
+use super::register_memory::{insert_memory, RegisterOptions};
+
+#[derive(Accounts)]
+pub struct RegisterMemoryWithGrant<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, registry.authority.as_ref()],
+        bump = registry.bump,
+        realloc = registry.grow_target(registry.to_account_info().data_len()),
+        realloc::payer = grantee,
+        realloc::zero = false,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    /// Holder of a write grant; pays for any growth its entry needs.
+    #[account(mut)]
+    pub grantee: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<RegisterMemoryWithGrant>,
+    content_hash: [u8; 32],
+    memory_type: u8,
+    importance_tier: u8,
+    memory_id: u64,
+    encrypted: bool,
+) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+
+    let grantee = ctx.accounts.grantee.key();
+    require!(
+        registry.has_write_grant(&grantee),
+        RegistryError::WriteGrantNotFound
+    );
+
+    let written = insert_memory(
+        registry,
+        content_hash,
+        memory_type,
+        importance_tier,
+        memory_id,
+        encrypted,
+        &RegisterOptions::default(),
+    )?;
+    // A duplicate ignored under `DUPLICATE_IGNORE` wrote nothing, so the
+    // grant is kept for a real write. Any realloc growth stays paid for.
+    if written {
+        registry.take_write_grant(&grantee);
+    }
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use super::register_memory::{insert_memory, RegisterMemory, RegisterOptions};
+
+pub(crate) fn handler(
+    ctx: Context<RegisterMemory>,
+    content_hash: [u8; 32],
+    memory_type: u8,
+    importance_tier: u8,
+    memory_id: u64,
+    encrypted: bool,
+    options: RegisterOptions,
+) -> Result<()> {
+    insert_memory(
+        &mut ctx.accounts.registry,
+        content_hash,
+        memory_type,
+        importance_tier,
+        memory_id,
+        encrypted,
+        &options,
+    )?;
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+use crate::error_context::fail_with_context;
+use crate::errors::RegistryError;
+
+#[derive(Accounts)]
+pub struct RegisterSchema<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<RegisterSchema>, schema_id: u16) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+
+    // 0 is the implicit "unstructured" schema and is always allowed.
+    require!(
+        schema_id != 0 && !registry.has_schema(schema_id),
+        RegistryError::InvalidSchema
+    );
+    let count = registry.schema_count as usize;
+    if count >= MemoryRegistry::MAX_SCHEMAS {
+        return Err(fail_with_context(
+            RegistryError::SchemaListFull,
+            schema_id as u64,
+            count as u64,
+            MemoryRegistry::MAX_SCHEMAS as u64,
+        ));
+    }
+
+    registry.schemas[count] = schema_id;
+    registry.schema_count += 1;
+    registry.bump_nonce();
+
+    msg!("Schema registered: {}", schema_id);
+    Ok(())
+}
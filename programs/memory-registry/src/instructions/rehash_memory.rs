@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use crate::errors::RegistryError;
+use crate::state::MemoryRegistry;
+use super::mutate_entry::MutateEntry;
+
+pub(crate) fn handler(
+    ctx: Context<MutateEntry>,
+    old_hash: [u8; 32],
+    new_hash: [u8; 32],
+) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+
+    let index = rehash_at(registry, &old_hash, new_hash, unix_now()?)?;
+    let entry = &registry.entries[index];
+    msg!(
+        "Memory rehashed: memory_id={} revision={}",
+        entry.memory_id,
+        entry.revision
+    );
+    Ok(())
+}
+
+/// Move the entry holding `old_hash` to `new_hash` at `now`, bumping its
+/// revision. The zero hash is rejected as it is on registration: it is the
+/// pending-reservation marker. Returns the entry's index.
+pub fn rehash_at(
+    registry: &mut MemoryRegistry,
+    old_hash: &[u8; 32],
+    new_hash: [u8; 32],
+    now: i64,
+) -> Result<usize> {
+    require!(new_hash != [0; 32], RegistryError::InvalidContentHash);
+    let index = registry
+        .find_entry(old_hash)
+        .ok_or(RegistryError::HashNotFound)?;
+    registry.require_mutable(index)?;
+    require!(
+        registry.find_entry(&new_hash).is_none(),
+        RegistryError::DuplicateHash
+    );
+
+    // Identity (memory_id, type, tier) stays; only the content hash moves.
+    registry.set_content_hash(index, new_hash);
+    registry.touch_entry(index, now);
+    let entry = &mut registry.entries[index];
+    entry.revision = entry.revision.saturating_add(1);
+    entry.timestamp = now;
+    Ok(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MemoryEntry;
+
+    fn registry_of(hashes: &[u8]) -> MemoryRegistry {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        for &h in hashes {
+            registry.push_entry(MemoryEntry {
+                content_hash: [h; 32],
+                ..Default::default()
+            });
+        }
+        registry
+    }
+
+    fn sorted_hashes(registry: &MemoryRegistry) -> Vec<u8> {
+        registry
+            .sorted_index
+            .iter()
+            .map(|&i| registry.entries[i as usize].content_hash[0])
+            .collect()
+    }
+
+    #[test]
+    fn rehash_bumps_the_revision_and_resorts() {
+        let mut registry = registry_of(&[2, 5, 8]);
+        let index = rehash_at(&mut registry, &[2; 32], [9; 32], 1_000).unwrap();
+
+        assert_eq!(index, 0);
+        let entry = &registry.entries[0];
+        assert_eq!(entry.content_hash, [9; 32]);
+        assert_eq!(entry.revision, 1);
+        assert_eq!((entry.timestamp, entry.last_modified), (1_000, 1_000));
+        assert_eq!(sorted_hashes(&registry), vec![5, 8, 9]);
+        assert_eq!(registry.find_entry(&[9; 32]), Some(0));
+        assert!(registry.find_entry(&[2; 32]).is_none());
+    }
+
+    #[test]
+    fn rehash_rejects_a_collision_and_the_zero_hash() {
+        let mut registry = registry_of(&[2, 5]);
+        let err = rehash_at(&mut registry, &[2; 32], [5; 32], 1_000).unwrap_err();
+        assert_eq!(err, RegistryError::DuplicateHash.into());
+        let err = rehash_at(&mut registry, &[2; 32], [0; 32], 1_000).unwrap_err();
+        assert_eq!(err, RegistryError::InvalidContentHash.into());
+
+        assert_eq!(registry.entries[0].revision, 0);
+        assert_eq!(sorted_hashes(&registry), vec![2, 5]);
+    }
+}
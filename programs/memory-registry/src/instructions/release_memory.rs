@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use crate::state::MemoryEntry;
+use crate::errors::RegistryError;
+use super::mutate_entry::MutateEntry;
+
+pub(crate) fn handler(ctx: Context<MutateEntry>, content_hash: [u8; 32]) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+
+    let index = registry
+        .find_entry(&content_hash)
+        .ok_or(RegistryError::HashNotFound)?;
+    registry.entries[index].set_flag(MemoryEntry::FLAG_QUARANTINED, false);
+    registry.touch_entry(index, unix_now()?);
+
+    msg!("Memory released: memory_id={}", registry.entries[index].memory_id);
+    Ok(())
+}
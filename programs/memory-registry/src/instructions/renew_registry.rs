@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+use crate::errors::RegistryError;
+
+#[derive(Accounts)]
+pub struct RenewRegistry<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<RenewRegistry>, new_expires_at: i64) -> Result<()> {
+    // No `require_live`: renewing an expired registry is the point. Once
+    // `reap_registry` has closed the account there is nothing left to load.
+    let now = unix_now()?;
+    require!(
+        new_expires_at == 0 || new_expires_at > now,
+        RegistryError::InvalidExpiry
+    );
+
+    let registry = &mut ctx.accounts.registry;
+    let old_expires_at = registry.expires_at;
+    registry.expires_at = new_expires_at;
+    registry.bump_nonce();
+
+    msg!("Registry renewed: expires_at {} -> {}", old_expires_at, new_expires_at);
+    Ok(())
+}
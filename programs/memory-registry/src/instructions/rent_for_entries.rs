@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+use crate::state::MemoryRegistry;
+
+/// Only the rent sysvar: the answer depends on nothing else but `n`.
+#[derive(Accounts)]
+pub struct RentForEntries<'info> {
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub(crate) fn handler(ctx: Context<RentForEntries>, n: u32) -> Result<u64> {
+    Ok(MemoryRegistry::rent_for(&ctx.accounts.rent, n as usize))
+}
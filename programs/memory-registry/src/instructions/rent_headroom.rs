@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::MemoryRegistry;
+use super::read_registry::ReadRegistry;
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>) -> Result<u64> {
+    let registry = &ctx.accounts.registry;
+    let lamports = registry.to_account_info().lamports();
+    let rent = Rent::get()?;
+
+    // Sized from the entries actually stored, not the allocation: this is
+    // about the balance, independent of how much space is already allocated.
+    let used = registry.entries.len();
+    let floor = rent.minimum_balance(MemoryRegistry::space_for(used));
+    let per_slot = rent
+        .minimum_balance(MemoryRegistry::space_for(used + 1))
+        .saturating_sub(floor)
+        .max(1);
+    let mut headroom = lamports.saturating_sub(floor) / per_slot;
+
+    // `minimum_balance` goes through f64; step back if rounding overshot.
+    while headroom > 0
+        && rent.minimum_balance(MemoryRegistry::space_for(used + headroom as usize)) > lamports
+    {
+        headroom -= 1;
+    }
+
+    Ok(headroom)
+}
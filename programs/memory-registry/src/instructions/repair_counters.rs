@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+
+#[derive(Accounts)]
+pub struct RepairCounters<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<RepairCounters>) -> Result<bool> {
+    let registry = &mut ctx.accounts.registry;
+
+    // Same checks as `self_check`; a consistent registry is left untouched
+    // (nonce included) so repeating the call is harmless.
+    let mismatch = registry.derived_mismatch();
+    if let Some((field, stored, expected)) = mismatch {
+        registry.rebuild_derived();
+        registry.bump_nonce();
+        msg!(
+            "Derived state repaired: first mismatch field={} stored={} expected={}",
+            field,
+            stored,
+            expected
+        );
+    }
+
+    Ok(mismatch.is_some())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::account_size::{require_capacity, shrink_registry};
+use crate::pda::REGISTRY_SEED;
+use crate::state::{MemoryEntry, MemoryRegistry};
+
+#[derive(Accounts)]
+pub struct ReplaceAll<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Must sign as well when the registry has a co-authority set.
+    pub co_authority: Option<Signer<'info>>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<ReplaceAll>,
+    entries: Vec<MemoryEntry>,
+    expected_digest: [u8; 32],
+) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    registry.require_co_signed(ctx.accounts.co_authority.as_ref())?;
+
+    // Optimistic concurrency: the caller must have seen the current state.
+    registry.require_sync_token(&expected_digest)?;
+
+    // Same capacity rule as `commit_replace`, so both paths size the
+    // account the same way.
+    let replaced = entries.len();
+    require_capacity(&registry.to_account_info(), replaced)?;
+    registry.replace_entries(entries)?;
+
+    let refunded = shrink_registry(
+        &ctx.accounts.registry,
+        &ctx.accounts.authority.to_account_info(),
+        replaced,
+    )?;
+
+    msg!("Registry replaced: {} entries refunded={}", replaced, refunded);
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::account_size::require_rent_exempt;
+use crate::clock::unix_now;
+use crate::state::MemoryEntry;
+use crate::errors::RegistryError;
+use super::register_memory::{trip_circuit_breaker, RegisterMemory};
+
+pub(crate) fn handler(ctx: Context<RegisterMemory>, memory_id: u64) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    registry.require_unfrozen()?;
+    require_rent_exempt(&registry.to_account_info())?;
+
+    require!(
+        registry.find_reservation(memory_id).is_none(),
+        RegistryError::ReservationExists
+    );
+
+    registry.require_room()?;
+    let now = unix_now()?;
+
+    // Zero hash + pending: claims the slot (and reallocs for it, via the
+    // RegisterMemory constraint) without being visible to hash lookups.
+    registry.push_entry(MemoryEntry {
+        timestamp: now,
+        last_modified: now,
+        memory_id,
+        flags: MemoryEntry::FLAG_PENDING,
+        ..Default::default()
+    });
+    trip_circuit_breaker(registry, now);
+
+    msg!("Memory slot reserved: memory_id={}", memory_id);
+    Ok(())
+}
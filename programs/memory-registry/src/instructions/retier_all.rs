@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use crate::pda::REGISTRY_SEED;
+use crate::scan::WorkBudget;
+use crate::state::MemoryRegistry;
+use crate::errors::RegistryError;
+
+#[derive(Accounts)]
+pub struct RetierAll<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    pub authority: Signer<'info>,
+}
+
+/// Outcome of a `retier_all` call, returned via return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RetierResult {
+    /// Entries moved from `from_tier` to `to_tier` in this call.
+    pub retiered: u32,
+    /// Entry index to pass as `cursor` on the next call.
+    pub next_cursor: u32,
+    /// True once the scan has reached the end of `entries`.
+    pub done: bool,
+}
+
+pub(crate) fn handler(
+    ctx: Context<RetierAll>,
+    from_tier: u8,
+    to_tier: u8,
+    cursor: u32,
+    max_work: u32,
+) -> Result<RetierResult> {
+    require!(
+        from_tier <= 2 && to_tier <= 2,
+        RegistryError::InvalidImportanceTier
+    );
+
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    let len = registry.entries.len();
+
+    // Expiries are left as they were set at registration; only the tier moves.
+    // Sealed and immutable entries are skipped.
+    let now = unix_now()?;
+    let mut budget = WorkBudget::new(max_work);
+    let mut index = cursor as usize;
+    let mut retiered: u32 = 0;
+    while index < len && budget.tick() {
+        let entry = &registry.entries[index];
+        if !entry.is_pending()
+            && entry.importance_tier == from_tier
+            && from_tier != to_tier
+            && registry.require_mutable(index).is_ok()
+        {
+            registry.set_importance_tier(index, to_tier);
+            registry.touch_entry(index, now);
+            retiered += 1;
+        }
+        index += 1;
+    }
+
+    let done = index >= len;
+    msg!(
+        "Retier {}->{}: retiered={} next_cursor={} done={}",
+        from_tier,
+        to_tier,
+        retiered,
+        index,
+        done
+    );
+    Ok(RetierResult {
+        retiered,
+        next_cursor: index as u32,
+        done,
+    })
+}
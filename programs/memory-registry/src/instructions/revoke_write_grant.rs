@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::errors::RegistryError;
+use super::grant_single_write::ManageWriteGrant;
+
+pub(crate) fn handler(ctx: Context<ManageWriteGrant>, grantee: Pubkey) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+
+    // Revokes one grant; a grantee holding several keeps the rest.
+    require!(
+        registry.take_write_grant(&grantee),
+        RegistryError::WriteGrantNotFound
+    );
+
+    msg!("Write grant revoked: {}", grantee);
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+use crate::errors::RegistryError;
+
+#[derive(Accounts)]
+pub struct SealEpoch<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    pub authority: Signer<'info>,
+    /// Must sign as well when the registry has a co-authority set.
+    pub co_authority: Option<Signer<'info>>,
+}
+
+pub(crate) fn handler(ctx: Context<SealEpoch>, epoch: u32) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    registry.require_co_signed(ctx.accounts.co_authority.as_ref())?;
+
+    // Epochs seal in order, so "sealed" is just `epoch < current_epoch` and
+    // needs no per-epoch storage. Naming the epoch guards against a retried
+    // transaction sealing the next one too.
+    require!(epoch == registry.current_epoch, RegistryError::InvalidEpoch);
+    registry.current_epoch = epoch
+        .checked_add(1)
+        .ok_or(RegistryError::InvalidEpoch)?;
+    registry.bump_nonce();
+
+    msg!("Epoch sealed: {} (current epoch now {})", epoch, registry.current_epoch);
+    Ok(())
+}
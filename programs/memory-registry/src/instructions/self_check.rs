@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::error_context::fail_with_context;
+use crate::errors::RegistryError;
+use super::read_registry::ReadRegistry;
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>) -> Result<()> {
+    let registry = &ctx.accounts.registry;
+
+    // O(n) recount plus a full pass over `sorted_index`; meant for debugging
+    // via simulation rather than routine use on large registries.
+    if let Some((field, stored, expected)) = registry.derived_mismatch() {
+        return Err(fail_with_context(
+            RegistryError::InconsistentDerivedState,
+            field,
+            stored,
+            expected,
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use crate::errors::RegistryError;
+use super::mutate_entry::MutateEntry;
+
+pub(crate) fn handler(
+    ctx: Context<MutateEntry>,
+    content_hash: [u8; 32],
+    app_data: [u8; 32],
+) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+
+    let index = registry
+        .find_entry(&content_hash)
+        .ok_or(RegistryError::HashNotFound)?;
+    registry.require_mutable(index)?;
+
+    registry.entries[index].app_data = app_data;
+    registry.touch_entry(index, unix_now()?);
+
+    msg!("App data set: memory_id={}", registry.entries[index].memory_id);
+    Ok(())
+}
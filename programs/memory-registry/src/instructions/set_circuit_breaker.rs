@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+use crate::errors::RegistryError;
+
+#[derive(Accounts)]
+pub struct SetCircuitBreaker<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SetCircuitBreaker>,
+    burst_threshold: u32,
+    burst_window: i64,
+) -> Result<()> {
+    require!(
+        burst_threshold == 0 || burst_window > 0,
+        RegistryError::InvalidCircuitBreaker
+    );
+
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    registry.burst_threshold = burst_threshold;
+    registry.burst_window = burst_window;
+    // Start counting afresh under the new thresholds.
+    registry.burst_window_start = unix_now()?;
+    registry.burst_count = 0;
+    registry.bump_nonce();
+
+    msg!("Circuit breaker set: {} per {}s", burst_threshold, burst_window);
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+
+#[derive(Accounts)]
+pub struct SetCoAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    pub authority: Signer<'info>,
+    /// The current co-authority, if one is set.
+    pub co_authority: Option<Signer<'info>>,
+}
+
+pub(crate) fn handler(ctx: Context<SetCoAuthority>, co_authority: Pubkey) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    // Changing or clearing an existing co-authority is itself dual-control,
+    // otherwise the authority alone could drop it and act unilaterally.
+    registry.require_co_signed(ctx.accounts.co_authority.as_ref())?;
+
+    registry.co_authority = co_authority;
+    registry.bump_nonce();
+
+    msg!("Co-authority set: {}", co_authority);
+    Ok(())
+}
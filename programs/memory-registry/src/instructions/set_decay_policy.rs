@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+use crate::errors::RegistryError;
+
+#[derive(Accounts)]
+pub struct SetDecayPolicy<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<SetDecayPolicy>, decay_after_seconds: i64) -> Result<()> {
+    require!(decay_after_seconds >= 0, RegistryError::InvalidDecayPolicy);
+
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    registry.decay_after_seconds = decay_after_seconds;
+    registry.bump_nonce();
+
+    msg!("Decay policy set: {}s", decay_after_seconds);
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+use crate::errors::RegistryError;
+
+#[derive(Accounts)]
+pub struct SetDuplicatePolicy<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<SetDuplicatePolicy>, duplicate_policy: u8) -> Result<()> {
+    require!(
+        duplicate_policy <= MemoryRegistry::DUPLICATE_UPDATE,
+        RegistryError::InvalidDuplicatePolicy
+    );
+
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    registry.duplicate_policy = duplicate_policy;
+    registry.bump_nonce();
+
+    msg!("Duplicate policy set: {}", duplicate_policy);
+    Ok(())
+}
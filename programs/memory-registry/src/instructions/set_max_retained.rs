@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::account_size::shrink_registry;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+
+#[derive(Accounts)]
+pub struct SetMaxRetained<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Must sign as well when the registry has a co-authority set and the
+    /// new limit evicts entries.
+    pub co_authority: Option<Signer<'info>>,
+}
+
+pub(crate) fn handler(ctx: Context<SetMaxRetained>, max_retained: u32) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    // A limit below the stored count is a bulk removal, so it needs the same
+    // dual control as `clear_entries`.
+    if max_retained != 0 && registry.stored_count() > max_retained {
+        registry.require_co_signed(ctx.accounts.co_authority.as_ref())?;
+    }
+    registry.max_retained = max_retained;
+    registry.bump_nonce();
+
+    // Trim to the new limit now so the freed rent can be refunded here;
+    // registration only ever evicts enough to make room for one entry.
+    let evicted = if max_retained == 0 {
+        0
+    } else {
+        registry.evict_oldest(max_retained)
+    };
+
+    let remaining = registry.entries.len();
+    let refunded = shrink_registry(
+        &ctx.accounts.registry,
+        &ctx.accounts.authority.to_account_info(),
+        remaining,
+    )?;
+
+    msg!(
+        "Max retained set: {} evicted={} refunded={}",
+        max_retained,
+        evicted,
+        refunded
+    );
+    Ok(())
+}
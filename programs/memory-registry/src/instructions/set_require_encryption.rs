@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+
+#[derive(Accounts)]
+pub struct SetRequireEncryption<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<SetRequireEncryption>, require_encryption: bool) -> Result<()> {
+    // Applies to future writes only; existing plaintext entries stay.
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    registry.require_encryption = require_encryption;
+    registry.bump_nonce();
+
+    msg!("Require encryption set: {}", require_encryption);
+    Ok(())
+}
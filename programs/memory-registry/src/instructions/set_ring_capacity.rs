@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+
+#[derive(Accounts)]
+pub struct SetRingCapacity<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    pub authority: Signer<'info>,
+    /// Must sign as well when the registry has a co-authority set and the
+    /// new capacity is below the current episodic count.
+    pub co_authority: Option<Signer<'info>>,
+}
+
+pub(crate) fn handler(ctx: Context<SetRingCapacity>, ring_capacity: u32) -> Result<()> {
+    // Shrinking below the current episodic count evicts nothing now, but the
+    // next episodic registration trims the ring back down in one go, so it
+    // needs the same dual control as any other bulk removal.
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    if ring_capacity != 0 && registry.type_counts[0] > ring_capacity {
+        registry.require_co_signed(ctx.accounts.co_authority.as_ref())?;
+    }
+    registry.ring_capacity = ring_capacity;
+    registry.bump_nonce();
+
+    msg!("Ring capacity set: {}", ring_capacity);
+    Ok(())
+}
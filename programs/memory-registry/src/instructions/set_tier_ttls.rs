@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+use crate::errors::RegistryError;
+
+#[derive(Accounts)]
+pub struct SetTierTtls<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<SetTierTtls>, tier_ttls: [i64; 3]) -> Result<()> {
+    require!(tier_ttls.iter().all(|&ttl| ttl >= 0), RegistryError::InvalidTtl);
+
+    // Only affects future registrations; existing expiries are left as-is.
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    registry.tier_ttls = tier_ttls;
+    registry.bump_nonce();
+
+    msg!("Tier TTLs set: {:?}", tier_ttls);
+    Ok(())
+}
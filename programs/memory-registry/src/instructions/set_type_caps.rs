@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+
+#[derive(Accounts)]
+pub struct SetTypeCaps<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<SetTypeCaps>, type_caps: [u32; 4]) -> Result<()> {
+    // Caps below the current count are allowed: existing entries stay, new
+    // writes of that type are rejected until the count drops under the cap.
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    registry.type_caps = type_caps;
+    registry.bump_nonce();
+
+    msg!("Type caps set: {:?}", type_caps);
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::HEAP_LENGTH;
+use std::cmp::Ordering;
+use crate::raw_registry::RawRegistry;
+use crate::state::{MemoryEntry, MemoryRegistry};
+use crate::errors::RegistryError;
+
+#[derive(Accounts)]
+pub struct SortedView<'info> {
+    /// CHECK: Parsed by hand via `RawRegistry`, so only the returned page is
+    /// deserialized; ownership is checked here, the discriminator and
+    /// authority in the handler.
+    #[account(owner = crate::ID)]
+    pub registry: UncheckedAccount<'info>,
+    /// CHECK: Authority the registry must belong to (read-only listing).
+    pub authority: UncheckedAccount<'info>,
+}
+
+/// `sort_key` values for `sorted_view`.
+pub const SORT_BY_TIMESTAMP: u8 = 0;
+pub const SORT_BY_IMPORTANCE: u8 = 1;
+pub const SORT_BY_HASH: u8 = 2;
+
+/// Most entries `sorted_view` will order by timestamp or importance in one
+/// call: the order is a `u32` per entry, held to half the heap so the page
+/// and the runtime keep the rest. Hash order walks `sorted_index` in place
+/// and is not bounded.
+pub const MAX_SORT_ENTRIES: usize = HEAP_LENGTH / 2 / std::mem::size_of::<u32>();
+
+pub(crate) fn handler(
+    ctx: Context<SortedView>,
+    sort_key: u8,
+    ascending: bool,
+    start: u32,
+    limit: u32,
+) -> Result<Vec<MemoryEntry>> {
+    let data = ctx.accounts.registry.try_borrow_data()?;
+    let registry = RawRegistry::parse(&data)?;
+    // As in `verify_memory_fast`: the stored authority stands in for the
+    // seeds check.
+    require_keys_eq!(
+        registry.authority(),
+        ctx.accounts.authority.key(),
+        ErrorCode::ConstraintHasOne
+    );
+    registry.require_live()?;
+    sorted_page(&registry, sort_key, ascending, start, limit)
+}
+
+/// One page of `registry`'s listable entries in `sort_key` order.
+pub fn sorted_page(
+    registry: &RawRegistry,
+    sort_key: u8,
+    ascending: bool,
+    start: u32,
+    limit: u32,
+) -> Result<Vec<MemoryEntry>> {
+    require!(sort_key <= SORT_BY_HASH, RegistryError::InvalidSortKey);
+    require!(
+        limit as usize <= MemoryRegistry::MAX_PAGE_ENTRIES,
+        RegistryError::PageLimitExceeded
+    );
+    let listable = |i: usize| registry.is_live(i);
+    let (start, limit) = (start as usize, limit as usize);
+
+    if sort_key == SORT_BY_HASH {
+        let count = registry.entry_count();
+        let mut page = Vec::new();
+        let mut skipped = 0;
+        for step in 0..count {
+            if page.len() == limit {
+                break;
+            }
+            let slot = registry.sorted_slot(if ascending { step } else { count - 1 - step })?;
+            if !listable(slot) {
+                continue;
+            }
+            if skipped < start {
+                skipped += 1;
+            } else {
+                page.push(registry.entry(slot)?);
+            }
+        }
+        return Ok(page);
+    }
+
+    require!(
+        registry.entry_count() <= MAX_SORT_ENTRIES,
+        RegistryError::ScanTooLarge
+    );
+    let mut order: Vec<u32> = (0..registry.entry_count() as u32)
+        .filter(|&i| listable(i as usize))
+        .collect();
+    let end = start.saturating_add(limit).min(order.len());
+    if start >= end {
+        return Ok(Vec::new());
+    }
+
+    // Ties fall back to insertion order, so the ordering is total and pages
+    // are stable across calls.
+    let key = |i: u32| match sort_key {
+        SORT_BY_TIMESTAMP => registry.timestamp(i as usize),
+        _ => registry.importance_tier(i as usize) as i64,
+    };
+    let compare = |a: &u32, b: &u32| -> Ordering {
+        let by_key = if ascending {
+            key(*a).cmp(&key(*b))
+        } else {
+            key(*b).cmp(&key(*a))
+        };
+        by_key.then(a.cmp(b))
+    };
+
+    // Only the first `end` positions are needed: partition them out, then
+    // sort just that prefix.
+    if end < order.len() {
+        order.select_nth_unstable_by(end - 1, compare);
+        order.truncate(end);
+    }
+    order.sort_unstable_by(compare);
+
+    order[start..end]
+        .iter()
+        .map(|&i| registry.entry(i as usize))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Entries `(hash byte, timestamp, tier)`, with the hash byte also used
+    /// as `memory_id`.
+    fn registry_bytes(entries: &[(u8, i64, u8)], quarantined: &[u8]) -> Vec<u8> {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        for &(h, timestamp, importance_tier) in entries {
+            let mut entry = MemoryEntry {
+                content_hash: [h; 32],
+                timestamp,
+                importance_tier,
+                memory_id: h as u64,
+                ..Default::default()
+            };
+            entry.set_flag(MemoryEntry::FLAG_QUARANTINED, quarantined.contains(&h));
+            registry.push_entry(entry);
+        }
+        let mut data = Vec::new();
+        registry.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn ids(data: &[u8], sort_key: u8, ascending: bool, start: u32, limit: u32) -> Vec<u64> {
+        let registry = RawRegistry::parse(data).unwrap();
+        sorted_page(&registry, sort_key, ascending, start, limit)
+            .unwrap()
+            .iter()
+            .map(|e| e.memory_id)
+            .collect()
+    }
+
+    #[test]
+    fn orders_by_each_key_and_skips_quarantined() {
+        let data = registry_bytes(&[(3, 30, 0), (1, 10, 2), (4, 20, 1), (2, 40, 1)], &[4]);
+        assert_eq!(ids(&data, SORT_BY_TIMESTAMP, true, 0, 5), vec![1, 3, 2]);
+        assert_eq!(ids(&data, SORT_BY_TIMESTAMP, false, 0, 5), vec![2, 3, 1]);
+        assert_eq!(ids(&data, SORT_BY_HASH, true, 0, 5), vec![1, 2, 3]);
+        assert_eq!(ids(&data, SORT_BY_HASH, false, 0, 5), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn importance_ties_keep_insertion_order_across_pages() {
+        let data = registry_bytes(&[(5, 0, 1), (6, 0, 2), (7, 0, 1), (8, 0, 1)], &[]);
+        assert_eq!(ids(&data, SORT_BY_IMPORTANCE, true, 0, 2), vec![5, 7]);
+        assert_eq!(ids(&data, SORT_BY_IMPORTANCE, true, 2, 2), vec![8, 6]);
+        assert_eq!(ids(&data, SORT_BY_HASH, true, 1, 2), vec![6, 7]);
+        assert!(ids(&data, SORT_BY_TIMESTAMP, true, 4, 2).is_empty());
+    }
+
+    #[test]
+    fn sort_bound_fits_the_heap() {
+        assert_eq!(MAX_SORT_ENTRIES * std::mem::size_of::<u32>(), HEAP_LENGTH / 2);
+    }
+}
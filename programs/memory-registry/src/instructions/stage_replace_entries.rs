@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::pda::REGISTRY_SEED;
+use crate::state::{MemoryEntry, MemoryRegistry, ReplaceStaging};
+
+#[derive(Accounts)]
+#[instruction(entries: Vec<MemoryEntry>)]
+pub struct StageReplaceEntries<'info> {
+    #[account(
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    #[account(
+        mut,
+        seeds = [b"replace-staging", registry.key().as_ref()],
+        bump = staging.bump,
+        has_one = registry,
+        realloc = ReplaceStaging::space_for(staging.entries.len() + entries.len()),
+        realloc::payer = authority,
+        realloc::zero = false,
+    )]
+    pub staging: Account<'info, ReplaceStaging>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<StageReplaceEntries>, entries: Vec<MemoryEntry>) -> Result<()> {
+    ctx.accounts.registry.require_live()?;
+
+    // Validation is deferred to commit, where the full set is known.
+    let staging = &mut ctx.accounts.staging;
+    staging.entries.extend(entries);
+
+    msg!("Staged: {} entries total", staging.entries.len());
+    Ok(())
+}
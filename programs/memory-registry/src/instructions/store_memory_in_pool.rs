@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::clock::unix_now;
 use crate::state::{Pool, PoolMemoryRecord};
 
 #[derive(Accounts)]
@@ -40,7 +41,7 @@ pub struct StoreMemoryInPool<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<StoreMemoryInPool>,
     content_hash: [u8; 32],
     memory_id: u64,
@@ -66,7 +67,7 @@ pub fn handler(
     memory.author = ctx.accounts.author.key();
     memory.content_hash = content_hash;
     memory.memory_id = memory_id;
-    memory.timestamp = Clock::get()?.unix_timestamp;
+    memory.timestamp = unix_now()?;
     memory.fee_paid = write_fee;
     memory.citation_count = 0;
     memory.earnings = 0;
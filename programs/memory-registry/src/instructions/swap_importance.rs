@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use crate::errors::RegistryError;
+use super::mutate_entry::MutateEntry;
+
+pub(crate) fn handler(
+    ctx: Context<MutateEntry>,
+    hash_a: [u8; 32],
+    hash_b: [u8; 32],
+) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+
+    let index_a = registry
+        .find_entry(&hash_a)
+        .ok_or(RegistryError::HashNotFound)?;
+    let index_b = registry
+        .find_entry(&hash_b)
+        .ok_or(RegistryError::HashNotFound)?;
+    registry.require_mutable(index_a)?;
+    registry.require_mutable(index_b)?;
+
+    let tier_a = registry.entries[index_a].importance_tier;
+    let tier_b = registry.entries[index_b].importance_tier;
+    if index_a == index_b || tier_a == tier_b {
+        return Ok(());
+    }
+
+    // Each move goes through `set_importance_tier`, so `tier_counts` end up
+    // where they started.
+    let now = unix_now()?;
+    registry.set_importance_tier(index_a, tier_b);
+    registry.set_importance_tier(index_b, tier_a);
+    registry.touch_entry(index_a, now);
+    registry.touch_entry(index_b, now);
+
+    msg!(
+        "Importance swapped: memory_id={} <-> memory_id={}",
+        registry.entries[index_a].memory_id,
+        registry.entries[index_b].memory_id
+    );
+    Ok(())
+}
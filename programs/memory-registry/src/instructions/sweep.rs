@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use crate::account_size::shrink_registry;
+use crate::scan::WorkBudget;
+use crate::state::MemoryRegistry;
+use super::prune_and_compact::PruneAndCompact;
+
+/// Outcome of a `sweep` call, returned via return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SweepResult {
+    /// Expired entries removed.
+    pub removed: u32,
+    /// Lamports of freed rent credited to the authority.
+    pub refunded: u64,
+    /// Resumption cursor: entries below this index are still to be visited.
+    /// Pass it as `cursor` on the next call.
+    pub next_cursor: u32,
+    /// True once the scan has visited every entry.
+    pub done: bool,
+}
+
+pub(crate) fn handler(
+    ctx: Context<PruneAndCompact>,
+    cursor: u32,
+    max_work: u32,
+) -> Result<SweepResult> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    registry.require_co_signed(ctx.accounts.co_authority.as_ref())?;
+    let now = unix_now()?;
+
+    // Each removal shifts the tail of `entries` (O(n)), so the work budget
+    // caps how many entries are visited per call.
+    let mut budget = WorkBudget::new(max_work);
+    let (removed, next_cursor) = sweep_expired(registry, cursor, now, || budget.tick());
+
+    // Runs even when nothing was removed, so an earlier sweep that left the
+    // account oversized is finished off; a no-op on an already-tight account.
+    let remaining = registry.entries.len();
+    let refunded = shrink_registry(
+        &ctx.accounts.registry,
+        &ctx.accounts.authority.to_account_info(),
+        remaining,
+    )?;
+
+    msg!(
+        "Swept: removed={} remaining={} refunded={} next_cursor={}",
+        removed,
+        remaining,
+        refunded,
+        next_cursor
+    );
+    Ok(SweepResult {
+        removed,
+        refunded,
+        next_cursor,
+        done: next_cursor == 0,
+    })
+}
+
+/// Remove entries expired at `now` from below `cursor` (clamped to the
+/// entry count, so `u32::MAX` starts a fresh sweep), one visit per `tick`.
+/// Walks from the back so removals don't shift the indices still to visit.
+/// Expired entries that are sealed or immutable are kept: both outrank
+/// expiry. Returns the removed count and the cursor to resume from.
+pub fn sweep_expired(
+    registry: &mut MemoryRegistry,
+    cursor: u32,
+    now: i64,
+    mut tick: impl FnMut() -> bool,
+) -> (u32, u32) {
+    let mut removed: u32 = 0;
+    let mut index = registry.entries.len().min(cursor as usize);
+    while index > 0 && tick() {
+        index -= 1;
+        if registry.entries[index].is_expired(now) && registry.require_mutable(index).is_ok() {
+            registry.remove_entry(index);
+            removed += 1;
+        }
+    }
+    (removed, index as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account_size::shrink_plan;
+    use crate::state::MemoryEntry;
+
+    const NOW: i64 = 1_000;
+
+    /// Entries with the given `expires_at`s (0 never expires).
+    fn registry_expiring(expiries: &[i64]) -> MemoryRegistry {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        for (i, &expires_at) in expiries.iter().enumerate() {
+            registry.push_entry(MemoryEntry {
+                content_hash: [i as u8 + 1; 32],
+                expires_at,
+                ..Default::default()
+            });
+        }
+        registry
+    }
+
+    fn hashes_left(registry: &MemoryRegistry) -> Vec<u8> {
+        registry.entries.iter().map(|e| e.content_hash[0]).collect()
+    }
+
+    #[test]
+    fn removes_expired_entries_and_shrinks_with_a_refund() {
+        let mut registry = registry_expiring(&[500, 0, NOW, 2_000, 1]);
+        registry.entries[4].set_flag(MemoryEntry::FLAG_IMMUTABLE, true);
+        let rent = Rent::default();
+        let data_len = MemoryRegistry::space_for(registry.entries.len());
+        let lamports = rent.minimum_balance(data_len);
+
+        let (removed, next_cursor) = sweep_expired(&mut registry, u32::MAX, NOW, || true);
+        assert_eq!((removed, next_cursor), (2, 0));
+        assert_eq!(hashes_left(&registry), vec![2, 4, 5], "immutable entry stays");
+        assert_eq!(registry.memory_count, 3);
+
+        let (new_len, refund) = shrink_plan(&rent, data_len, lamports, 3).unwrap();
+        assert_eq!(new_len, MemoryRegistry::space_for(3));
+        assert!(new_len < data_len);
+        assert_eq!(refund, lamports - rent.minimum_balance(new_len));
+        assert!(refund > 0);
+    }
+
+    #[test]
+    fn a_repeat_with_nothing_expired_is_a_no_op() {
+        let mut registry = registry_expiring(&[500, 0, 2_000]);
+        sweep_expired(&mut registry, u32::MAX, NOW, || true);
+        let rent = Rent::default();
+        let data_len = MemoryRegistry::space_for(registry.entries.len());
+        let nonce = registry.nonce;
+
+        let (removed, next_cursor) = sweep_expired(&mut registry, u32::MAX, NOW, || true);
+        assert_eq!((removed, next_cursor), (0, 0));
+        assert_eq!(hashes_left(&registry), vec![2, 3]);
+        assert_eq!(registry.nonce, nonce);
+        let lamports = rent.minimum_balance(data_len);
+        assert_eq!(shrink_plan(&rent, data_len, lamports, registry.entries.len()), None);
+    }
+
+    #[test]
+    fn resumes_from_the_cursor_when_the_budget_runs_out() {
+        let mut registry = registry_expiring(&[1, 1, 1, 1]);
+        let mut units = 3;
+        let (removed, next_cursor) = sweep_expired(&mut registry, u32::MAX, NOW, || {
+            units -= 1;
+            units >= 0
+        });
+        assert_eq!((removed, next_cursor), (3, 1));
+        assert_eq!(hashes_left(&registry), vec![1]);
+
+        let (removed, next_cursor) = sweep_expired(&mut registry, next_cursor, NOW, || true);
+        assert_eq!((removed, next_cursor), (1, 0));
+        assert!(registry.entries.is_empty());
+    }
+}
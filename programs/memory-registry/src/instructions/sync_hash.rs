@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+use super::read_registry::ReadRegistry;
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>) -> Result<[u8; 32]> {
+    // `nonce` moves on every mutation, so this token changes whenever the
+    // registry does and is stable across pure reads.
+    Ok(ctx.accounts.registry.sync_token())
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::account_size::shrink_registry;
+use crate::pda::REGISTRY_SEED;
+use crate::state::MemoryRegistry;
+
+#[derive(Accounts)]
+pub struct TrimCapacity<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, authority.key().as_ref()],
+        bump = registry.bump,
+        has_one = authority,
+    )]
+    pub registry: Account<'info, MemoryRegistry>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<TrimCapacity>) -> Result<u64> {
+    ctx.accounts.registry.require_live()?;
+
+    // Leave one realloc increment of headroom so the next few registrations
+    // don't immediately pay to grow back.
+    let slots = ctx.accounts.registry.entries.len() + MemoryRegistry::REALLOC_INCREMENT;
+    let refunded = shrink_registry(
+        &ctx.accounts.registry,
+        &ctx.accounts.authority.to_account_info(),
+        slots,
+    )?;
+
+    msg!("Capacity trimmed: slots={} refunded={}", slots, refunded);
+    Ok(refunded)
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use super::read_registry::ReadRegistry;
+
+/// Timestamp range of one memory type; both 0 when the type has no entries.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct TimeBounds {
+    pub earliest: i64,
+    pub latest: i64,
+}
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>) -> Result<[TimeBounds; 4]> {
+    let registry = &ctx.accounts.registry;
+
+    let mut bounds: [Option<TimeBounds>; 4] = [None; 4];
+    for entry in registry.entries.iter().filter(|e| e.is_live()) {
+        let slot = &mut bounds[entry.memory_type as usize];
+        *slot = Some(match *slot {
+            None => TimeBounds {
+                earliest: entry.timestamp,
+                latest: entry.timestamp,
+            },
+            Some(b) => TimeBounds {
+                earliest: b.earliest.min(entry.timestamp),
+                latest: b.latest.max(entry.timestamp),
+            },
+        });
+    }
+
+    Ok(bounds.map(Option::unwrap_or_default))
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use super::set_circuit_breaker::SetCircuitBreaker;
+
+pub(crate) fn handler(ctx: Context<SetCircuitBreaker>) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+    registry.frozen = false;
+    // The burst that tripped the breaker shouldn't re-trip it on the next
+    // registration.
+    registry.burst_window_start = unix_now()?;
+    registry.burst_count = 0;
+    registry.bump_nonce();
+
+    msg!("Registry unfrozen");
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use crate::errors::RegistryError;
+use super::mutate_entry::MutateEntry;
+
+pub(crate) fn handler(
+    ctx: Context<MutateEntry>,
+    content_hash: [u8; 32],
+    new_memory_id: u64,
+) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.require_live()?;
+
+    require!(new_memory_id != 0, RegistryError::InvalidMemoryId);
+    let index = registry
+        .find_entry(&content_hash)
+        .ok_or(RegistryError::HashNotFound)?;
+    registry.require_mutable(index)?;
+
+    // Registration doesn't enforce unique ids, but a re-ID must not make two
+    // entries point at the same Supabase row.
+    require!(
+        !registry
+            .entries
+            .iter()
+            .enumerate()
+            .any(|(i, e)| i != index && e.memory_id == new_memory_id),
+        RegistryError::DuplicateMemoryId
+    );
+
+    let old_memory_id = registry.entries[index].memory_id;
+    registry.entries[index].memory_id = new_memory_id;
+    registry.touch_entry(index, unix_now()?);
+
+    msg!("Memory id updated: {} -> {}", old_memory_id, new_memory_id);
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+use crate::errors::RegistryError;
+use super::read_registry::ReadRegistry;
+
+pub(crate) fn handler(
+    ctx: Context<ReadRegistry>,
+    content_hash: [u8; 32],
+    as_of_ts: i64,
+) -> Result<()> {
+    let registry = &ctx.accounts.registry;
+    registry.require_live()?;
+
+    let held = registry
+        .find_active(&content_hash)
+        .is_some_and(|index| registry.entries[index].registered_by(as_of_ts));
+    require!(held, RegistryError::HashNotFound);
+
+    Ok(())
+}
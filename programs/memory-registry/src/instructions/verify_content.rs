@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::errors::RegistryError;
+use crate::state::MemoryRegistry;
+use super::read_registry::ReadRegistry;
+
+/// Largest serialized transaction, in bytes: the IPv6 minimum MTU less the
+/// IP and UDP headers, as `solana_packet::PACKET_DATA_SIZE` (which this
+/// program's dependencies don't re-export).
+pub const PACKET_DATA_SIZE: usize = 1280 - 40 - 8;
+
+/// Bytes of the smallest transaction that can carry a `verify_content` call,
+/// less the content itself: one signature; a legacy message with its header,
+/// three account keys (the fee payer doubling as the authority, the
+/// registry, this program) and the blockhash; and one instruction naming two
+/// accounts, its data the discriminator and the borsh length prefix. The
+/// two-byte data length holds for any content over 127 bytes.
+const VERIFY_CONTENT_TX_OVERHEAD: usize = (1 + 64) // signatures
+    + 3 // message header
+    + (1 + 3 * 32) // account keys
+    + 32 // recent blockhash
+    + 1 // instruction count
+    + 1 // program id index
+    + (1 + 2) // account indices
+    + 2 // instruction data length
+    + 8 // discriminator
+    + 4; // content length prefix
+
+/// Longest content `verify_content` will hash: the most that fits in a
+/// transaction, so no content a client can send is refused by length alone.
+/// The check restates the transaction limit and bounds the sha256 syscall
+/// cost.
+pub const MAX_VERIFY_CONTENT_LEN: usize = PACKET_DATA_SIZE - VERIFY_CONTENT_TX_OVERHEAD;
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>, content: Vec<u8>) -> Result<()> {
+    let registry = &ctx.accounts.registry;
+    registry.require_live()?;
+    require_content_registered(registry, &content)
+}
+
+/// sha256 of `content`, the same syscall clients are expected to match
+/// off-chain. Fails with `ContentTooLong` past `MAX_VERIFY_CONTENT_LEN`.
+pub fn content_hash(content: &[u8]) -> Result<[u8; 32]> {
+    require!(
+        content.len() <= MAX_VERIFY_CONTENT_LEN,
+        RegistryError::ContentTooLong
+    );
+    Ok(hash(content).to_bytes())
+}
+
+/// Fail unless the hash of `content` is registered and active.
+pub fn require_content_registered(registry: &MemoryRegistry, content: &[u8]) -> Result<()> {
+    let content_hash = content_hash(content)?;
+    require!(!registry.entries.is_empty(), RegistryError::RegistryEmpty);
+    require!(
+        registry.find_active(&content_hash).is_some(),
+        RegistryError::HashNotFound
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MemoryEntry;
+
+    #[test]
+    fn registered_content_verifies_by_its_hash() {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        let err = require_content_registered(&registry, b"remembered").unwrap_err();
+        assert_eq!(err, RegistryError::RegistryEmpty.into());
+
+        registry.push_entry(MemoryEntry {
+            content_hash: hash(b"remembered").to_bytes(),
+            ..Default::default()
+        });
+        require_content_registered(&registry, b"remembered").unwrap();
+        let err = require_content_registered(&registry, b"forgotten").unwrap_err();
+        assert_eq!(err, RegistryError::HashNotFound.into());
+    }
+
+    #[test]
+    fn the_length_bound_is_what_a_transaction_can_carry() {
+        assert_eq!(MAX_VERIFY_CONTENT_LEN, 1016);
+        assert!(content_hash(&[7; MAX_VERIFY_CONTENT_LEN]).is_ok());
+        let err = content_hash(&[7; MAX_VERIFY_CONTENT_LEN + 1]).unwrap_err();
+        assert_eq!(err, RegistryError::ContentTooLong.into());
+    }
+}
@@ -1,28 +1,19 @@
 use anchor_lang::prelude::*;
-use crate::state::MemoryRegistry;
 use crate::errors::RegistryError;
+use super::read_registry::ReadRegistry;
 
-#[derive(Accounts)]
-#[instruction(content_hash: [u8; 32])]
-pub struct VerifyMemory<'info> {
-    #[account(
-        seeds = [b"memory-registry", authority.key().as_ref()],
-        bump = registry.bump,
-        has_one = authority,
-    )]
-    pub registry: Account<'info, MemoryRegistry>,
-    /// CHECK: Authority used for PDA derivation only (read-only verification).
-    pub authority: UncheckedAccount<'info>,
-}
-
-pub fn handler(ctx: Context<VerifyMemory>, content_hash: [u8; 32]) -> Result<()> {
+pub(crate) fn handler(ctx: Context<ReadRegistry>, content_hash: [u8; 32]) -> Result<()> {
     let registry = &ctx.accounts.registry;
+    registry.require_live()?;
+
+    // Distinguish "nothing registered yet" from a genuine miss so clients can
+    // tell a fresh registry apart from an unknown hash.
+    require!(!registry.entries.is_empty(), RegistryError::RegistryEmpty);
 
-    for entry in &registry.entries {
-        if entry.content_hash == content_hash {
-            return Ok(());
-        }
-    }
+    require!(
+        registry.find_active(&content_hash).is_some(),
+        RegistryError::HashNotFound
+    );
 
-    Err(RegistryError::HashNotFound.into())
+    Ok(())
 }
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::errors::RegistryError;
+use crate::raw_registry::RawRegistry;
+
+#[derive(Accounts)]
+pub struct VerifyMemoryFast<'info> {
+    /// CHECK: Parsed by hand via `RawRegistry`; ownership is checked here,
+    /// the discriminator and authority in the handler.
+    #[account(owner = crate::ID)]
+    pub registry: UncheckedAccount<'info>,
+    /// CHECK: Authority the registry must belong to (read-only verification).
+    pub authority: UncheckedAccount<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<VerifyMemoryFast>, content_hash: [u8; 32]) -> Result<()> {
+    let data = ctx.accounts.registry.try_borrow_data()?;
+    let registry = RawRegistry::parse(&data)?;
+
+    // A program-owned registry records its authority, and `initialize` only
+    // creates one at that authority's PDA, so this stands in for the seeds
+    // check without a PDA derivation.
+    require_keys_eq!(
+        registry.authority(),
+        ctx.accounts.authority.key(),
+        ErrorCode::ConstraintHasOne
+    );
+
+    registry.require_live()?;
+
+    require!(registry.entry_count() > 0, RegistryError::RegistryEmpty);
+    require!(registry.contains(&content_hash), RegistryError::HashNotFound);
+
+    Ok(())
+}
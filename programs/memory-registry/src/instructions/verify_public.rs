@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+use crate::errors::RegistryError;
+use super::read_registry::ReadRegistry;
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>, content_hash: [u8; 32]) -> Result<()> {
+    let registry = &ctx.accounts.registry;
+    registry.require_live()?;
+
+    // Private entries answer exactly like absent ones.
+    let public = registry
+        .find_active(&content_hash)
+        .is_some_and(|index| registry.entries[index].is_public());
+    require!(public, RegistryError::HashNotFound);
+
+    Ok(())
+}
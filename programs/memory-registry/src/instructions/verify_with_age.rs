@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use crate::errors::RegistryError;
+use super::read_registry::ReadRegistry;
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>, content_hash: [u8; 32]) -> Result<i64> {
+    let registry = &ctx.accounts.registry;
+    registry.require_live()?;
+
+    let index = registry
+        .find_active(&content_hash)
+        .ok_or(RegistryError::HashNotFound)?;
+
+    // Age since the last registration write; clamped so a timestamp slightly
+    // ahead of this validator's clock reads as 0, not negative.
+    let now = unix_now()?;
+    Ok(now.saturating_sub(registry.entries[index].timestamp).max(0))
+}
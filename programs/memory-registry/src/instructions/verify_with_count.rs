@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::errors::RegistryError;
+use super::read_registry::ReadRegistry;
+
+pub(crate) fn handler(ctx: Context<ReadRegistry>, content_hash: [u8; 32]) -> Result<u64> {
+    let registry = &ctx.accounts.registry;
+    registry.require_live()?;
+
+    // Same outcomes as `verify_memory`; the count rides along on a hit.
+    require!(!registry.entries.is_empty(), RegistryError::RegistryEmpty);
+    require!(
+        registry.find_active(&content_hash).is_some(),
+        RegistryError::HashNotFound
+    );
+
+    Ok(registry.memory_count)
+}
@@ -1,14 +1,47 @@
+// `#[program]` emits Anchor's IDL instructions at the crate root, and
+// `__idl_resize_account` calls the deprecated `AccountInfo::realloc`. Nothing
+// there can carry an attribute, so the allow has to be crate-level; every
+// module of ours re-enables the lint.
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 
+#[warn(deprecated)]
+pub mod account_size;
+#[warn(deprecated)]
+pub mod clock;
+// CPI helpers for consumer programs; depend with `features = ["cpi"]`.
+#[cfg(feature = "cpi")]
+#[warn(deprecated)]
+pub mod cpi_client;
+#[warn(deprecated)]
+pub mod ed25519;
+#[warn(deprecated)]
+pub mod error_context;
+#[warn(deprecated)]
 pub mod errors;
+#[warn(deprecated)]
+pub mod events;
+#[warn(deprecated)]
 pub mod instructions;
+#[warn(deprecated)]
+pub mod migration;
+#[warn(deprecated)]
+pub mod pda;
+#[warn(deprecated)]
+pub mod raw_registry;
+#[warn(deprecated)]
+pub mod scan;
+#[warn(deprecated)]
 pub mod state;
 
 use instructions::*;
+use state::MemoryEntry;
 
 // Program ID — generated via `anchor keys list`
 declare_id!("GPc2p7rNNC23kd396zKgsCCTsRH1H3APxDUDRXLTVfdo");
 
+#[warn(deprecated)]
 #[program]
 pub mod memory_registry {
     use super::*;
@@ -18,6 +51,27 @@ pub mod memory_registry {
         instructions::initialize::handler(ctx)
     }
 
+    /// Create a registry that expires as a whole at `expires_at` (0 = never).
+    /// Past it, writes and verifications fail with `RegistryExpired` and
+    /// `reap_registry` can close it.
+    pub fn initialize_with_expiry(ctx: Context<Initialize>, expires_at: i64) -> Result<()> {
+        instructions::initialize_with_expiry::handler(ctx, expires_at)
+    }
+
+    /// Close an expired registry, refunding its rent to the original
+    /// authority. Permissionless: anyone may submit it once the registry has
+    /// expired; fails with `RegistryNotExpired` before then.
+    pub fn reap_registry(ctx: Context<ReapRegistry>) -> Result<()> {
+        instructions::reap_registry::handler(ctx)
+    }
+
+    /// Set a new registry lifetime (`0` = never; otherwise must be in the
+    /// future, `InvalidExpiry`). Works on a registry that has already expired
+    /// but not yet been reaped, re-enabling writes and verifications.
+    pub fn renew_registry(ctx: Context<RenewRegistry>, new_expires_at: i64) -> Result<()> {
+        instructions::renew_registry::handler(ctx, new_expires_at)
+    }
+
     /// Register a memory's content hash in the on-chain registry.
     pub fn register_memory(
         ctx: Context<RegisterMemory>,
@@ -37,11 +91,830 @@ pub mod memory_registry {
         )
     }
 
+    /// Register one memory as the holder of a single-use write grant (see
+    /// `grant_single_write`). The grantee signs and pays for any growth; one
+    /// grant is consumed unless the hash is a duplicate the registry ignores,
+    /// and without one this fails with `WriteGrantNotFound`.
+    pub fn register_memory_with_grant(
+        ctx: Context<RegisterMemoryWithGrant>,
+        content_hash: [u8; 32],
+        memory_type: u8,
+        importance_tier: u8,
+        memory_id: u64,
+        encrypted: bool,
+    ) -> Result<()> {
+        instructions::register_memory_with_grant::handler(
+            ctx,
+            content_hash,
+            memory_type,
+            importance_tier,
+            memory_id,
+            encrypted,
+        )
+    }
+
+    /// Register a memory stamped with an externally signed time instead of
+    /// the clock. The preceding instruction must be an Ed25519 precompile
+    /// check of the registry's `time_oracle` signing
+    /// `timestamp_message(content_hash, attestation.timestamp)`; otherwise,
+    /// or with no oracle set, this fails with `InvalidTimestampSignature`.
+    /// A timestamp more than `MAX_TIMESTAMP_SKEW` seconds ahead of the clock
+    /// fails with `TimestampInFuture`.
+    pub fn register_memory_timestamped(
+        ctx: Context<RegisterMemoryTimestamped>,
+        content_hash: [u8; 32],
+        memory_type: u8,
+        importance_tier: u8,
+        memory_id: u64,
+        encrypted: bool,
+        attestation: TimestampAttestation,
+    ) -> Result<()> {
+        instructions::register_memory_timestamped::handler(
+            ctx,
+            content_hash,
+            memory_type,
+            importance_tier,
+            memory_id,
+            encrypted,
+            attestation,
+        )
+    }
+
+    /// Set the key trusted to sign entry timestamps for
+    /// `register_memory_timestamped` (`Pubkey::default()` = none).
+    pub fn set_time_oracle(ctx: Context<SetTimeOracle>, time_oracle: Pubkey) -> Result<()> {
+        instructions::set_time_oracle::handler(ctx, time_oracle)
+    }
+
+    /// Let `grantee` register exactly one memory. Up to
+    /// `MemoryRegistry::MAX_WRITE_GRANTS` grants may be outstanding
+    /// (`WriteGrantsFull`); a key may hold several.
+    pub fn grant_single_write(ctx: Context<ManageWriteGrant>, grantee: Pubkey) -> Result<()> {
+        instructions::grant_single_write::handler(ctx, grantee)
+    }
+
+    /// Withdraw one unused write grant from `grantee`.
+    pub fn revoke_write_grant(ctx: Context<ManageWriteGrant>, grantee: Pubkey) -> Result<()> {
+        instructions::revoke_write_grant::handler(ctx, grantee)
+    }
+
+    /// Register a memory with optional per-entry settings (explicit expiry,
+    /// content schema, preview hash, event correlation id, confidence,
+    /// immutability, public visibility). Default options behave exactly like
+    /// `register_memory`.
+    pub fn register_memory_with_options(
+        ctx: Context<RegisterMemory>,
+        content_hash: [u8; 32],
+        memory_type: u8,
+        importance_tier: u8,
+        memory_id: u64,
+        encrypted: bool,
+        options: RegisterOptions,
+    ) -> Result<()> {
+        instructions::register_memory_with_options::handler(
+            ctx,
+            content_hash,
+            memory_type,
+            importance_tier,
+            memory_id,
+            encrypted,
+            options,
+        )
+    }
+
+    /// Register a memory only if `requires_hash` (its parent) is already in
+    /// the registry; fails with `DependencyMissing` otherwise.
+    pub fn register_memory_dependent(
+        ctx: Context<RegisterMemory>,
+        content_hash: [u8; 32],
+        memory_type: u8,
+        importance_tier: u8,
+        memory_id: u64,
+        encrypted: bool,
+        requires_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::register_memory_dependent::handler(
+            ctx,
+            content_hash,
+            memory_type,
+            importance_tier,
+            memory_id,
+            encrypted,
+            requires_hash,
+        )
+    }
+
+    /// Register `content` under its on-chain sha256, so clients never compute
+    /// or pass the hash. Content is capped at `MAX_VERIFY_CONTENT_LEN` bytes
+    /// (`ContentTooLong`); otherwise as `register_memory`.
+    pub fn register_content(
+        ctx: Context<RegisterMemory>,
+        content: Vec<u8>,
+        memory_type: u8,
+        importance_tier: u8,
+        memory_id: u64,
+        encrypted: bool,
+    ) -> Result<()> {
+        instructions::register_content::handler(
+            ctx,
+            content,
+            memory_type,
+            importance_tier,
+            memory_id,
+            encrypted,
+        )
+    }
+
+    /// Register a memory synthesized from up to four sources, recording
+    /// their `memory_id`s in `derived_from`. Fails with `SourceMissing` if
+    /// any source is not registered.
+    pub fn register_memory_derived(
+        ctx: Context<RegisterMemory>,
+        content_hash: [u8; 32],
+        memory_type: u8,
+        importance_tier: u8,
+        memory_id: u64,
+        encrypted: bool,
+        derived_from: Vec<u64>,
+    ) -> Result<()> {
+        instructions::register_memory_derived::handler(
+            ctx,
+            content_hash,
+            memory_type,
+            importance_tier,
+            memory_id,
+            encrypted,
+            derived_from,
+        )
+    }
+
+    /// Remove the named hashes (at most `max_work` processed, fewer if compute
+    /// runs low) and shrink the account to fit, refunding freed rent to the
+    /// authority. Sealed, immutable and quarantined entries are skipped, as
+    /// `sweep` skips them. Returns the removed and skipped counts and a
+    /// resumption cursor.
+    pub fn prune_and_compact(
+        ctx: Context<PruneAndCompact>,
+        hashes: Vec<[u8; 32]>,
+        max_work: u32,
+    ) -> Result<PruneResult> {
+        instructions::prune_and_compact::handler(ctx, hashes, max_work)
+    }
+
+    /// Lower the importance tier of entries not modified (`last_modified`)
+    /// for a full `decay_after_seconds` period, one tier per period. Decay
+    /// doesn't itself count as a modification. Permissionless and resumable:
+    /// scans from `cursor` for at most `max_work` entries.
+    pub fn decay(ctx: Context<Decay>, cursor: u32, max_work: u32) -> Result<DecayResult> {
+        instructions::decay::handler(ctx, cursor, max_work)
+    }
+
+    /// Remove entries that share a content hash, keeping the earliest copy,
+    /// then shrink the account and refund freed rent. Resumable: scans from
+    /// `cursor` (a `sorted_index` position) for at most `max_work` steps.
+    /// Co-signed like `prune_and_compact`.
+    pub fn dedupe(
+        ctx: Context<PruneAndCompact>,
+        cursor: u32,
+        max_work: u32,
+    ) -> Result<DedupeResult> {
+        instructions::dedupe::handler(ctx, cursor, max_work)
+    }
+
+    /// Shrink an over-allocated account to its entries plus
+    /// `REALLOC_INCREMENT` spare slots, keeping every entry, and refund the
+    /// freed rent to the authority. Returns lamports refunded (0 if already
+    /// at or below that size).
+    pub fn trim_capacity(ctx: Context<TrimCapacity>) -> Result<u64> {
+        instructions::trim_capacity::handler(ctx)
+    }
+
+    /// Remove expired entries and shrink the account to fit, refunding freed
+    /// rent to the authority. Resumable: scans down from `cursor` (pass
+    /// `u32::MAX` to start) for at most `max_work` entries. Safe to repeat:
+    /// with nothing expired it removes nothing and refunds nothing.
+    /// Co-signed like `prune_and_compact`.
+    pub fn sweep(
+        ctx: Context<PruneAndCompact>,
+        cursor: u32,
+        max_work: u32,
+    ) -> Result<SweepResult> {
+        instructions::sweep::handler(ctx, cursor, max_work)
+    }
+
+    /// Prove to a third party that the registry authority holds `content_hash`
+    /// without the authority signing the transaction. The authority pre-signs
+    /// `challenge` off-chain; the transaction must include the matching
+    /// Ed25519 precompile instruction immediately before this one.
+    pub fn prove_ownership(
+        ctx: Context<ProveOwnership>,
+        content_hash: [u8; 32],
+        challenge: [u8; 32],
+        signature: [u8; 64],
+    ) -> Result<OwnershipProof> {
+        instructions::prove_ownership::handler(ctx, content_hash, challenge, signature)
+    }
+
+    /// Claim a registry slot for `memory_id` before its content hash is known.
+    /// The placeholder is pending: zero hash, invisible to verification.
+    pub fn reserve_memory(ctx: Context<RegisterMemory>, memory_id: u64) -> Result<()> {
+        instructions::reserve_memory::handler(ctx, memory_id)
+    }
+
+    /// Fill in a pending reservation's hash and metadata, making it live.
+    /// Checked and limited like a fresh registration: type caps, the
+    /// episodic ring and `max_retained` all apply.
+    pub fn finalize_memory(
+        ctx: Context<FinalizeMemory>,
+        memory_id: u64,
+        content_hash: [u8; 32],
+        memory_type: u8,
+        importance_tier: u8,
+        encrypted: bool,
+    ) -> Result<()> {
+        instructions::finalize_memory::handler(
+            ctx,
+            memory_id,
+            content_hash,
+            memory_type,
+            importance_tier,
+            encrypted,
+        )
+    }
+
+    /// Drop a pending reservation without finalizing it.
+    pub fn cancel_reservation(ctx: Context<CancelReservation>, memory_id: u64) -> Result<()> {
+        instructions::cancel_reservation::handler(ctx, memory_id)
+    }
+
+    /// Add a content schema id to the registry's allow-list. Entries may then
+    /// be registered with that `schema_id`.
+    pub fn register_schema(ctx: Context<RegisterSchema>, schema_id: u16) -> Result<()> {
+        instructions::register_schema::handler(ctx, schema_id)
+    }
+
+    /// Replace the content hash of an existing entry (content edited, identity
+    /// kept). Bumps the entry's revision and refreshes its timestamp.
+    pub fn rehash_memory(
+        ctx: Context<MutateEntry>,
+        old_hash: [u8; 32],
+        new_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::rehash_memory::handler(ctx, old_hash, new_hash)
+    }
+
+    /// Point an entry at a new Supabase `memory_id` (after a re-ID or
+    /// merge). The id must be non-zero (`InvalidMemoryId`) and unused by any
+    /// other entry (`DuplicateMemoryId`); sealed and immutable entries refuse.
+    pub fn update_memory_id(
+        ctx: Context<MutateEntry>,
+        content_hash: [u8; 32],
+        new_memory_id: u64,
+    ) -> Result<()> {
+        instructions::update_memory_id::handler(ctx, content_hash, new_memory_id)
+    }
+
+    /// Exchange the importance tiers of two registered entries, for relative
+    /// re-ranking. Sealed and immutable entries refuse; equal tiers are a
+    /// no-op.
+    pub fn swap_importance(
+        ctx: Context<MutateEntry>,
+        hash_a: [u8; 32],
+        hash_b: [u8; 32],
+    ) -> Result<()> {
+        instructions::swap_importance::handler(ctx, hash_a, hash_b)
+    }
+
+    /// Overwrite an entry's opaque `app_data`, leaving every other field as
+    /// is. Sealed and immutable entries refuse.
+    pub fn set_app_data(
+        ctx: Context<MutateEntry>,
+        content_hash: [u8; 32],
+        app_data: [u8; 32],
+    ) -> Result<()> {
+        instructions::set_app_data::handler(ctx, content_hash, app_data)
+    }
+
+    /// Set an entry's embedding `cluster_id` (0 = unassigned), leaving every
+    /// other field as is. Sealed and immutable entries refuse.
+    pub fn assign_cluster(
+        ctx: Context<MutateEntry>,
+        content_hash: [u8; 32],
+        cluster_id: u16,
+    ) -> Result<()> {
+        instructions::assign_cluster::handler(ctx, content_hash, cluster_id)
+    }
+
+    /// Hold an entry for review: recall reads treat it as absent, and it
+    /// refuses every update and removal, pruning and eviction included,
+    /// until `release_memory`. Allowed on sealed and immutable entries.
+    pub fn quarantine_memory(ctx: Context<MutateEntry>, content_hash: [u8; 32]) -> Result<()> {
+        instructions::quarantine_memory::handler(ctx, content_hash)
+    }
+
+    /// Lift a quarantine, making the entry visible and mutable again.
+    pub fn release_memory(ctx: Context<MutateEntry>, content_hash: [u8; 32]) -> Result<()> {
+        instructions::release_memory::handler(ctx, content_hash)
+    }
+
+    /// Atomically replace every entry, provided the registry still matches
+    /// `expected_digest` (its current `sync_hash`); fails with `StateChanged`
+    /// otherwise. Recomputes all derived state and shrinks the account to
+    /// fit; growing it is up to `grow_registry` (`RegistryTooSmall`). Entries
+    /// already registered keep their stored timestamp and the rest are
+    /// stamped now, whatever timestamps are supplied. For sets too large for
+    /// one transaction, use the staging flow below.
+    pub fn replace_all(
+        ctx: Context<ReplaceAll>,
+        entries: Vec<MemoryEntry>,
+        expected_digest: [u8; 32],
+    ) -> Result<()> {
+        instructions::replace_all::handler(ctx, entries, expected_digest)
+    }
+
+    /// Remove every entry but keep the account and its settings; resets all
+    /// derived state and shrinks back to `INITIAL_CAPACITY` slots, refunding
+    /// the freed rent. Fails as `replace_all` if any entry is sealed or
+    /// immutable.
+    pub fn clear_entries(ctx: Context<ClearEntries>) -> Result<()> {
+        instructions::clear_entries::handler(ctx)
+    }
+
+    /// Grow the registry account towards room for `slots` entries, by at
+    /// most 10 KiB per call (the runtime's per-instruction limit), with the
+    /// authority paying the rent. Returns true once the target is reached;
+    /// repeat until then. Works on registries awaiting `migrate_registry`
+    /// too. `slots` above `MAX_REGISTRY_ENTRIES` fails with `RegistryFull`.
+    pub fn grow_registry(ctx: Context<GrowRegistry>, slots: u32) -> Result<bool> {
+        instructions::grow_registry::handler(ctx, slots)
+    }
+
+    /// Rewrite a registry created before `schema_version` existed into the
+    /// current layout, converting each entry's `encrypted` byte into
+    /// `FLAG_ENCRYPTED`, dropping its `_padding`, and recomputing derived
+    /// state. Grow the account to fit first with `grow_registry`
+    /// (`RegistryTooSmall`). Returns false, changing nothing, if it is
+    /// already current.
+    pub fn migrate_registry(ctx: Context<MigrateRegistry>) -> Result<bool> {
+        instructions::migrate_registry::handler(ctx)
+    }
+
+    /// Pre-flight for `migrate_registry`: the stored schema version against
+    /// this program's, and the gap. Reads the version from its fixed offset,
+    /// so it works on accounts that no longer deserialize.
+    pub fn needs_migration(ctx: Context<NeedsMigration>) -> Result<MigrationStatus> {
+        instructions::needs_migration::handler(ctx)
+    }
+
+    /// Open a staging buffer for a chunked replace.
+    pub fn open_replace_staging(ctx: Context<OpenReplaceStaging>) -> Result<()> {
+        instructions::open_replace_staging::handler(ctx)
+    }
+
+    /// Append a chunk of entries to the staging buffer.
+    pub fn stage_replace_entries(
+        ctx: Context<StageReplaceEntries>,
+        entries: Vec<MemoryEntry>,
+    ) -> Result<()> {
+        instructions::stage_replace_entries::handler(ctx, entries)
+    }
+
+    /// Stage one page of an `export_canonical` backup, like
+    /// `stage_replace_entries`. Fails with `UnsupportedExportVersion` unless
+    /// the page was written in this program's format.
+    pub fn import_canonical(ctx: Context<ImportCanonical>, export: CanonicalExport) -> Result<()> {
+        instructions::import_canonical::handler(ctx, export)
+    }
+
+    /// Swap the staged entries into the registry (same digest guard as
+    /// `replace_all`) and close the staging buffer. A staged set can need
+    /// more than the 10 KiB one instruction may grow an account by, so the
+    /// registry must first be grown to fit with `grow_registry`
+    /// (`RegistryTooSmall` otherwise).
+    pub fn commit_replace(ctx: Context<CommitReplace>, expected_digest: [u8; 32]) -> Result<()> {
+        instructions::commit_replace::handler(ctx, expected_digest)
+    }
+
+    /// Discard the staging buffer without touching the registry.
+    pub fn cancel_replace(ctx: Context<CancelReplace>) -> Result<()> {
+        instructions::cancel_replace::handler(ctx)
+    }
+
+    /// Move every entry at `from_tier` to `to_tier`, resumably: scans from
+    /// `cursor`, examining at most `max_work` entries per call, and returns
+    /// the next cursor. Tier counters stay consistent.
+    pub fn retier_all(
+        ctx: Context<RetierAll>,
+        from_tier: u8,
+        to_tier: u8,
+        cursor: u32,
+        max_work: u32,
+    ) -> Result<RetierResult> {
+        instructions::retier_all::handler(ctx, from_tier, to_tier, cursor, max_work)
+    }
+
+    /// Seal `epoch` (which must be the current epoch) and open the next one.
+    /// Entries registered in a sealed epoch reject updates, rehashes and
+    /// removals with `EpochSealed`.
+    pub fn seal_epoch(ctx: Context<SealEpoch>, epoch: u32) -> Result<()> {
+        instructions::seal_epoch::handler(ctx, epoch)
+    }
+
+    /// Set (or, with `Pubkey::default()`, clear) the co-authority that must
+    /// co-sign destructive instructions. Once one is set, changing it also
+    /// needs its signature.
+    pub fn set_co_authority(ctx: Context<SetCoAuthority>, co_authority: Pubkey) -> Result<()> {
+        instructions::set_co_authority::handler(ctx, co_authority)
+    }
+
+    /// Set how `register_memory` treats an already-registered hash:
+    /// 0 = reject (default), 1 = ignore (no-op), 2 = update metadata in place.
+    pub fn set_duplicate_policy(
+        ctx: Context<SetDuplicatePolicy>,
+        duplicate_policy: u8,
+    ) -> Result<()> {
+        instructions::set_duplicate_policy::handler(ctx, duplicate_policy)
+    }
+
+    /// Set default TTLs (seconds) per importance tier; 0 = never expires.
+    /// Applied to registrations that don't pass an explicit expiry.
+    pub fn set_tier_ttls(ctx: Context<SetTierTtls>, tier_ttls: [i64; 3]) -> Result<()> {
+        instructions::set_tier_ttls::handler(ctx, tier_ttls)
+    }
+
+    /// Arm the circuit breaker: more than `burst_threshold` registrations
+    /// within `burst_window` seconds freezes the registry (0 = disabled).
+    /// Restarts the counting window.
+    pub fn set_circuit_breaker(
+        ctx: Context<SetCircuitBreaker>,
+        burst_threshold: u32,
+        burst_window: i64,
+    ) -> Result<()> {
+        instructions::set_circuit_breaker::handler(ctx, burst_threshold, burst_window)
+    }
+
+    /// Clear a tripped circuit breaker so registrations resume, starting a
+    /// fresh counting window.
+    pub fn unfreeze_registry(ctx: Context<SetCircuitBreaker>) -> Result<()> {
+        instructions::unfreeze_registry::handler(ctx)
+    }
+
+    /// Set the importance decay period in seconds (0 = disabled).
+    pub fn set_decay_policy(ctx: Context<SetDecayPolicy>, decay_after_seconds: i64) -> Result<()> {
+        instructions::set_decay_policy::handler(ctx, decay_after_seconds)
+    }
+
+    /// Keep only the newest `ring_capacity` episodic memories (0 = off): past
+    /// it, registering an episodic memory evicts the oldest one by timestamp.
+    /// A capacity below the current episodic count must be co-signed when a
+    /// co-authority is set.
+    pub fn set_ring_capacity(ctx: Context<SetRingCapacity>, ring_capacity: u32) -> Result<()> {
+        instructions::set_ring_capacity::handler(ctx, ring_capacity)
+    }
+
+    /// Keep only the newest `max_retained` memories by timestamp (0 = off):
+    /// past it, registering evicts the oldest. Lowering it trims at once and
+    /// refunds the freed rent to the authority; a limit that evicts anything
+    /// must be co-signed when a co-authority is set.
+    pub fn set_max_retained(ctx: Context<SetMaxRetained>, max_retained: u32) -> Result<()> {
+        instructions::set_max_retained::handler(ctx, max_retained)
+    }
+
+    /// Require every future write to be encrypted (default off); plaintext
+    /// registrations then fail with `EncryptionRequired`.
+    pub fn set_require_encryption(
+        ctx: Context<SetRequireEncryption>,
+        require_encryption: bool,
+    ) -> Result<()> {
+        instructions::set_require_encryption::handler(ctx, require_encryption)
+    }
+
+    /// Set per-memory-type entry caps (indexed by memory type; 0 = unlimited).
+    pub fn set_type_caps(ctx: Context<SetTypeCaps>, type_caps: [u32; 4]) -> Result<()> {
+        instructions::set_type_caps::handler(ctx, type_caps)
+    }
+
     /// Verify a content hash exists in the registry (read-only).
-    pub fn verify_memory(ctx: Context<VerifyMemory>, content_hash: [u8; 32]) -> Result<()> {
+    /// Fails with `RegistryEmpty` on a registry with no entries, `HashNotFound`
+    /// otherwise.
+    pub fn verify_memory(ctx: Context<ReadRegistry>, content_hash: [u8; 32]) -> Result<()> {
         instructions::verify_memory::handler(ctx, content_hash)
     }
 
+    /// Same result as `verify_memory`, but binary-searches the raw account
+    /// bytes instead of deserializing the registry, so its cost no longer
+    /// grows with the entry count and it is not bounded by the 32 KiB heap.
+    /// Errors as in `verify_memory`.
+    pub fn verify_memory_fast(
+        ctx: Context<VerifyMemoryFast>,
+        content_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::verify_memory_fast::handler(ctx, content_hash)
+    }
+
+    /// Recompute every derived field (`memory_count`, `type_counts`,
+    /// `tier_counts`, `encrypted_count`, `sorted_index`) from `entries` and
+    /// fail with `InconsistentDerivedState` on the first mismatch; the field
+    /// code and values are left in return data as an `ErrorContext`.
+    pub fn self_check(ctx: Context<ReadRegistry>) -> Result<()> {
+        instructions::self_check::handler(ctx)
+    }
+
+    /// Self-heal counterpart to `self_check`: if any derived field disagrees
+    /// with `entries`, rebuild them all from `entries` and return true;
+    /// otherwise change nothing and return false.
+    pub fn repair_counters(ctx: Context<RepairCounters>) -> Result<bool> {
+        instructions::repair_counters::handler(ctx)
+    }
+
+    /// Hash `content` on-chain (sha256) and verify the result is registered
+    /// (read-only), so clients never compute the hash themselves. Content is
+    /// capped at `MAX_VERIFY_CONTENT_LEN` bytes (`ContentTooLong`); misses fail
+    /// as in `verify_memory`.
+    pub fn verify_content(ctx: Context<ReadRegistry>, content: Vec<u8>) -> Result<()> {
+        instructions::verify_content::handler(ctx, content)
+    }
+
+    /// Verify a content hash was registered at or before `as_of_ts`
+    /// (read-only). Fails with `HashNotFound` if the entry is absent or newer.
+    pub fn verify_before(
+        ctx: Context<ReadRegistry>,
+        content_hash: [u8; 32],
+        as_of_ts: i64,
+    ) -> Result<()> {
+        instructions::verify_before::handler(ctx, content_hash, as_of_ts)
+    }
+
+    /// Verify a content hash the authority has published (`FLAG_PUBLIC`).
+    /// Callable by anyone; private and absent entries both fail with
+    /// `HashNotFound`.
+    pub fn verify_public(ctx: Context<ReadRegistry>, content_hash: [u8; 32]) -> Result<()> {
+        instructions::verify_public::handler(ctx, content_hash)
+    }
+
+    /// Verify a content hash and return its age in seconds (`now - timestamp`)
+    /// via return data. Fails with `HashNotFound` on a miss.
+    pub fn verify_with_age(ctx: Context<ReadRegistry>, content_hash: [u8; 32]) -> Result<i64> {
+        instructions::verify_with_age::handler(ctx, content_hash)
+    }
+
+    /// `verify_memory` that also returns the registry's `memory_count` via
+    /// return data on a hit.
+    pub fn verify_with_count(ctx: Context<ReadRegistry>, content_hash: [u8; 32]) -> Result<u64> {
+        instructions::verify_with_count::handler(ctx, content_hash)
+    }
+
+    /// Return the registry's allowed content schema ids (read-only).
+    pub fn list_schemas(ctx: Context<ReadRegistry>) -> Result<Vec<u16>> {
+        instructions::list_schemas::handler(ctx)
+    }
+
+    /// Compare the live content-hash sets of two registries (read-only): returns
+    /// intersection and union sizes and the Jaccard ratio in basis points.
+    /// Both accounts are read in place rather than deserialized. Fails with
+    /// `ScanTooLarge` above `MAX_JACCARD_ENTRIES` combined entries.
+    pub fn jaccard(ctx: Context<Jaccard>) -> Result<JaccardResult> {
+        instructions::jaccard::handler(ctx)
+    }
+
+    /// Page through entries matching a memory type and importance tier, either
+    /// of which may be `FILTER_ANY` (255). Paging as in `list_never_expiring`.
+    pub fn filter(
+        ctx: Context<ReadRegistry>,
+        memory_type: u8,
+        importance_tier: u8,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<MemoryEntry>> {
+        instructions::filter::handler(ctx, memory_type, importance_tier, start, limit)
+    }
+
+    /// Page through entries ordered by `sort_key` (`SORT_BY_TIMESTAMP`,
+    /// `SORT_BY_IMPORTANCE` or `SORT_BY_HASH`), computed per call and never
+    /// stored. Ties keep insertion order. Timestamp and importance orders fail
+    /// with `ScanTooLarge` above `MAX_SORT_ENTRIES`.
+    pub fn sorted_view(
+        ctx: Context<SortedView>,
+        sort_key: u8,
+        ascending: bool,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<MemoryEntry>> {
+        instructions::sorted_view::handler(ctx, sort_key, ascending, start, limit)
+    }
+
+    /// Page through entries with `confidence >= min_confidence`. Paging as in
+    /// `list_never_expiring`.
+    pub fn list_by_min_confidence(
+        ctx: Context<ReadRegistry>,
+        min_confidence: u8,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<MemoryEntry>> {
+        instructions::list_by_min_confidence::handler(ctx, min_confidence, start, limit)
+    }
+
+    /// Page through entries written after `since_ts` (`last_modified >
+    /// since_ts`), for delta sync. Removals leave no entry behind, and decay
+    /// doesn't stamp `last_modified`, so neither shows up here; `sync_hash`
+    /// still changes for both. Paging as in `list_never_expiring`.
+    pub fn changed_since(
+        ctx: Context<ReadRegistry>,
+        since_ts: i64,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<MemoryEntry>> {
+        instructions::changed_since::handler(ctx, since_ts, start, limit)
+    }
+
+    /// Page through the entries assigned to `cluster_id`. Paging as in
+    /// `list_never_expiring`.
+    pub fn list_by_cluster(
+        ctx: Context<ReadRegistry>,
+        cluster_id: u16,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<MemoryEntry>> {
+        instructions::list_by_cluster::handler(ctx, cluster_id, start, limit)
+    }
+
+    /// Page through entries of one `content_kind` (`MemoryEntry::CONTENT_*`;
+    /// `InvalidContentKind` otherwise). Paging as in `list_never_expiring`.
+    pub fn list_by_content_kind(
+        ctx: Context<ReadRegistry>,
+        content_kind: u8,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<MemoryEntry>> {
+        instructions::list_by_content_kind::handler(ctx, content_kind, start, limit)
+    }
+
+    /// Page through entries that never expire (`expires_at == 0`), for
+    /// cleanup audits. `start` skips that many matches; `limit` is capped at
+    /// `MemoryRegistry::MAX_PAGE_ENTRIES`.
+    pub fn list_never_expiring(
+        ctx: Context<ReadRegistry>,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<MemoryEntry>> {
+        instructions::list_never_expiring::handler(ctx, start, limit)
+    }
+
+    /// Look up a content hash and return the full entry, or `None` on a miss
+    /// (read-only, via return data). Value-returning counterpart to
+    /// `verify_memory`, and like it fails with `RegistryExpired` once the
+    /// registry has expired.
+    pub fn lookup(
+        ctx: Context<ReadRegistry>,
+        content_hash: [u8; 32],
+    ) -> Result<Option<MemoryEntry>> {
+        instructions::lookup::handler(ctx, content_hash)
+    }
+
+    /// How many more entries the registry's current lamport balance keeps
+    /// rent-exempt (read-only, via return data). About the balance, not the
+    /// allocated space reported by `get_account_metrics`.
+    pub fn rent_headroom(ctx: Context<ReadRegistry>) -> Result<u64> {
+        instructions::rent_headroom::handler(ctx)
+    }
+
+    /// Rent-exempt lamports a registry holding `n` entries would need, for
+    /// capacity planning (read-only, via return data; takes only the rent
+    /// sysvar). Off-chain, `MemoryRegistry::rent_for` computes the same.
+    pub fn rent_for_entries(ctx: Context<RentForEntries>, n: u32) -> Result<u64> {
+        instructions::rent_for_entries::handler(ctx, n)
+    }
+
+    /// Return the preview hash stored alongside `content_hash` (all zeros if
+    /// the entry has none). Fails with `HashNotFound` on a miss.
+    pub fn get_preview(ctx: Context<ReadRegistry>, content_hash: [u8; 32]) -> Result<[u8; 32]> {
+        instructions::get_preview::handler(ctx, content_hash)
+    }
+
+    /// Count live entries by age, `(now - timestamp) / bucket_seconds`, into
+    /// `num_buckets` buckets (read-only, via return data). Entries older than
+    /// the last bucket are counted in it.
+    pub fn age_histogram(
+        ctx: Context<ReadRegistry>,
+        bucket_seconds: i64,
+        num_buckets: u8,
+    ) -> Result<Vec<u32>> {
+        instructions::age_histogram::handler(ctx, bucket_seconds, num_buckets)
+    }
+
+    /// The entry just before (`NEIGHBOR_PREVIOUS`) or after (`NEIGHBOR_NEXT`)
+    /// the named one on the timeline, ordered by timestamp then hash, or
+    /// `None` at either end (read-only, via return data). The named hash must
+    /// be registered (`HashNotFound`).
+    pub fn neighbor_by_time(
+        ctx: Context<ReadRegistry>,
+        content_hash: [u8; 32],
+        direction: u8,
+    ) -> Result<Option<MemoryEntry>> {
+        instructions::neighbor_by_time::handler(ctx, content_hash, direction)
+    }
+
+    /// Source `memory_id`s recorded for `content_hash` (read-only, via return
+    /// data); empty if it wasn't derived. Fails with `HashNotFound` on a miss.
+    pub fn provenance_of(ctx: Context<ReadRegistry>, content_hash: [u8; 32]) -> Result<Vec<u64>> {
+        instructions::provenance_of::handler(ctx, content_hash)
+    }
+
+    /// Index of the first of `hashes` that is not registered, or `None` if
+    /// all are (read-only, via return data). For finding where an ordered
+    /// local log stops matching the chain.
+    pub fn first_missing(ctx: Context<ReadRegistry>, hashes: Vec<[u8; 32]>) -> Result<Option<u32>> {
+        instructions::first_missing::handler(ctx, hashes)
+    }
+
+    /// Batched `lookup`: one result per input hash, in order, `None` for
+    /// misses (read-only, via return data). At most `MAX_BATCH_LOOKUP` hashes
+    /// per call (`PageLimitExceeded`).
+    pub fn get_memories_batch(
+        ctx: Context<ReadRegistry>,
+        hashes: Vec<[u8; 32]>,
+    ) -> Result<Vec<Option<MemoryEntry>>> {
+        instructions::get_memories_batch::handler(ctx, hashes)
+    }
+
+    /// Return the registry's authority, canonical bump, schema version,
+    /// co-authority and lifetime (read-only, via return data) so clients can
+    /// confirm they hold the right account without re-deriving it.
+    pub fn get_registry_info(ctx: Context<ReadRegistry>) -> Result<RegistryInfo> {
+        instructions::get_registry_info::handler(ctx)
+    }
+
+    /// Earliest and latest `timestamp` per memory type, indexed by type
+    /// (read-only, via return data); both 0 for a type with no entries.
+    pub fn type_time_bounds(ctx: Context<ReadRegistry>) -> Result<[TimeBounds; 4]> {
+        instructions::type_time_bounds::handler(ctx)
+    }
+
+    /// Count and share (basis points) of live entries that are encrypted
+    /// (read-only, via return data). One scan.
+    pub fn encryption_stats(ctx: Context<ReadRegistry>) -> Result<EncryptionCoverage> {
+        instructions::encryption_stats::handler(ctx)
+    }
+
+    /// How evenly live entries spread across memory types, as normalized
+    /// Shannon entropy in basis points (read-only, via return data): 10_000
+    /// for an even split, 0 when every entry has one type or there are none.
+    /// One scan.
+    pub fn diversity_score(ctx: Context<ReadRegistry>) -> Result<u16> {
+        instructions::diversity_score::handler(ctx)
+    }
+
+    /// Count and share (basis points) of live entries whose expiry has
+    /// passed (read-only, via return data): a dry run of `sweep`, with
+    /// `reclaimable` counting what it would actually remove. One scan.
+    pub fn expired_fraction(ctx: Context<ReadRegistry>) -> Result<ExpiredCoverage> {
+        instructions::expired_fraction::handler(ctx)
+    }
+
+    /// One page of a versioned backup (read-only, via return data): entries
+    /// in slots `start..start + limit` behind a format header. Restore with
+    /// `open_replace_staging`, `import_canonical` per page, `commit_replace`;
+    /// as with any replace, only hashes still held keep their `timestamp`.
+    pub fn export_canonical(
+        ctx: Context<ReadRegistry>,
+        start: u32,
+        limit: u32,
+    ) -> Result<CanonicalExport> {
+        instructions::export_canonical::handler(ctx, start, limit)
+    }
+
+    /// Storage-health diagnostic: how many live entries share each
+    /// `prefix_len`-byte hash prefix (distinct prefixes, largest cluster,
+    /// mean per prefix x100), read-only via return data.
+    pub fn prefix_collision_report(
+        ctx: Context<ReadRegistry>,
+        prefix_len: u8,
+    ) -> Result<PrefixCollisions> {
+        instructions::prefix_collision_report::handler(ctx, prefix_len)
+    }
+
+    /// Return account size, slot usage, balance and rent-exempt minimum for
+    /// the registry (read-only, via return data).
+    pub fn get_account_metrics(ctx: Context<ReadRegistry>) -> Result<AccountMetrics> {
+        instructions::get_account_metrics::handler(ctx)
+    }
+
+    /// Break the registry account's bytes into stored-entry payload, layout
+    /// overhead (header, index slots, pending reservations) and unused
+    /// realloc slack (read-only, via return data).
+    pub fn get_storage_efficiency(
+        ctx: Context<ReadRegistry>,
+    ) -> Result<StorageEfficiency> {
+        instructions::get_storage_efficiency::handler(ctx)
+    }
+
+    /// Return a compact sync token — sha256(memory_count, nonce) — that
+    /// changes on any registry mutation. Light clients re-fetch only when it
+    /// differs from the last token they saw.
+    pub fn sync_hash(ctx: Context<ReadRegistry>) -> Result<[u8; 32]> {
+        instructions::sync_hash::handler(ctx)
+    }
+
     /// Create a new shared memory pool with configurable write + citation fees.
     pub fn create_pool(
         ctx: Context<CreatePool>,
@@ -0,0 +1,238 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::errors::RegistryError;
+use crate::state::{MemoryEntry, MemoryRegistry};
+
+/// Byte offset of `schema_version`, right after `bump`.
+pub const SCHEMA_VERSION_OFFSET: usize = 8 + 32 + 8 + 1;
+
+/// Version 0 (the original layout): discriminator(8) + authority(32) +
+/// memory_count(8) + bump(1) + entries vec_prefix(4).
+pub const V0_BASE_SIZE: usize = 8 + 32 + 8 + 1 + 4;
+
+/// Borsh size of a version 0 entry: hash(32) + timestamp(8) + memory_type(1)
+/// + importance_tier(1) + memory_id(8) + encrypted(1) + _padding(3).
+///
+/// Space was allocated at a 56-byte stride, but entries were serialized
+/// packed at this size, so 5 of every 56 allocated bytes carried nothing:
+/// `_padding` plus 2 bytes of over-reservation. Version 1 has no padding
+/// field and allocates exactly the 190 bytes each entry serializes to;
+/// `migrate_v0` drops `_padding` unread.
+pub const V0_ENTRY_SIZE: usize = 54;
+
+/// Layout version of a registry account's raw bytes: 0 for the original
+/// untagged layout, otherwise the tagged `schema_version`. `None` if `data`
+/// is not a registry account at all.
+pub fn stored_schema_version(data: &[u8]) -> Option<u32> {
+    if data.len() < SCHEMA_VERSION_OFFSET + 4 || data[..8] != *MemoryRegistry::DISCRIMINATOR {
+        return None;
+    }
+    let word = read_u32(data, SCHEMA_VERSION_OFFSET);
+    if word & MemoryRegistry::VERSION_TAG == 0 {
+        Some(0)
+    } else {
+        Some(word & !MemoryRegistry::VERSION_TAG)
+    }
+}
+
+/// Rewrite a version 0 registry in place into the current layout. `data`
+/// must already have room for `space_for(count)` (see `grow_registry`).
+/// Returns the number of entries carried over.
+///
+/// Entries only grow, so each one's new slot starts past any old entry not
+/// yet read; converting from the back never overwrites unread bytes. The
+/// header goes last, since the new one covers the old entries. Settings
+/// start at their defaults; derived state is recomputed from the entries.
+pub fn migrate_v0(data: &mut [u8]) -> Result<u32> {
+    require!(
+        stored_schema_version(data) == Some(0) && data.len() >= V0_BASE_SIZE,
+        RegistryError::UnsupportedSchemaVersion
+    );
+    let authority = Pubkey::new_from_array(data[8..40].try_into().unwrap());
+    let bump = data[48];
+    let count = read_u32(data, V0_BASE_SIZE - 4) as usize;
+    require!(
+        V0_BASE_SIZE + count * V0_ENTRY_SIZE <= data.len(),
+        ErrorCode::AccountDidNotDeserialize
+    );
+    require!(
+        data.len() >= MemoryRegistry::space_for(count),
+        RegistryError::RegistryTooSmall
+    );
+
+    let mut registry = MemoryRegistry::new(authority, bump, 0);
+    let entries_offset = MemoryRegistry::BASE_SIZE - 4;
+    for index in (0..count).rev() {
+        let old = V0_BASE_SIZE + index * V0_ENTRY_SIZE;
+        let timestamp = i64::from_le_bytes(data[old + 32..old + 40].try_into().unwrap());
+        let mut entry = MemoryEntry {
+            content_hash: data[old..old + 32].try_into().unwrap(),
+            timestamp,
+            // Version 0 checked nothing beyond these ranges, so clamp rather
+            // than fail the whole migration on one bad entry.
+            memory_type: data[old + 40].min(3),
+            importance_tier: data[old + 41].min(2),
+            memory_id: u64::from_le_bytes(data[old + 42..old + 50].try_into().unwrap()),
+            last_modified: timestamp,
+            ..Default::default()
+        };
+        // The standalone `encrypted` byte becomes `FLAG_ENCRYPTED`.
+        entry.set_flag(MemoryEntry::FLAG_ENCRYPTED, data[old + 50] != 0);
+        registry.count_entry(&entry);
+
+        let new = entries_offset + index * MemoryRegistry::ENTRY_SIZE;
+        entry.serialize(&mut &mut data[new..new + MemoryRegistry::ENTRY_SIZE])?;
+    }
+
+    let hash_at = |data: &[u8], i: u32| -> [u8; 32] {
+        let at = entries_offset + i as usize * MemoryRegistry::ENTRY_SIZE;
+        data[at..at + 32].try_into().unwrap()
+    };
+    let mut sorted_index: Vec<u32> = (0..count as u32).collect();
+    sorted_index.sort_by_cached_key(|&i| hash_at(data, i));
+    let index_offset = entries_offset + count * MemoryRegistry::ENTRY_SIZE;
+    data[index_offset..index_offset + 4].copy_from_slice(&(count as u32).to_le_bytes());
+    for (pos, slot) in sorted_index.iter().enumerate() {
+        let at = index_offset + 4 + pos * MemoryRegistry::INDEX_ENTRY_SIZE;
+        data[at..at + 4].copy_from_slice(&slot.to_le_bytes());
+    }
+
+    registry.memory_count = count as u64;
+    registry.nonce = 1;
+    let mut header = Vec::with_capacity(MemoryRegistry::BASE_SIZE);
+    registry.try_serialize(&mut header)?;
+    let header_len = MemoryRegistry::BASE_SIZE - 8;
+    data[..header_len].copy_from_slice(&header[..header_len]);
+    data[header_len..header_len + 4].copy_from_slice(&(count as u32).to_le_bytes());
+
+    Ok(count as u32)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v0_entry(
+        hash: u8,
+        timestamp: i64,
+        memory_type: u8,
+        memory_id: u64,
+        encrypted: bool,
+    ) -> Vec<u8> {
+        let mut bytes = vec![hash; 32];
+        bytes.extend_from_slice(&timestamp.to_le_bytes());
+        bytes.push(memory_type);
+        bytes.push(1);
+        bytes.extend_from_slice(&memory_id.to_le_bytes());
+        bytes.push(encrypted as u8);
+        bytes.extend_from_slice(&[0; 3]);
+        bytes
+    }
+
+    fn v0_account(entries: &[Vec<u8>], len: usize) -> Vec<u8> {
+        let mut data = MemoryRegistry::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[7; 32]);
+        data.extend_from_slice(&(entries.len() as u64).to_le_bytes());
+        data.push(254);
+        data.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+        for entry in entries {
+            data.extend_from_slice(entry);
+        }
+        data.resize(len, 0);
+        data
+    }
+
+    #[test]
+    fn migrates_v0_entries_and_derived_state() {
+        let entries = [
+            v0_entry(3, 100, 0, 11, false),
+            v0_entry(1, 200, 2, 12, true),
+            v0_entry(2, 300, 0, 13, false),
+        ];
+        let mut data = v0_account(&entries, MemoryRegistry::space_for(3));
+        assert_eq!(stored_schema_version(&data), Some(0));
+
+        assert_eq!(migrate_v0(&mut data).unwrap(), 3);
+        assert_eq!(
+            stored_schema_version(&data),
+            Some(MemoryRegistry::SCHEMA_VERSION)
+        );
+
+        let registry = MemoryRegistry::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(registry.authority, Pubkey::new_from_array([7; 32]));
+        assert_eq!(registry.bump, 254);
+        assert_eq!(registry.memory_count, 3);
+        assert_eq!(registry.type_counts, [2, 0, 1, 0]);
+        assert_eq!(registry.tier_counts, [0, 3, 0]);
+        assert_eq!(registry.encrypted_count, 1);
+        assert_eq!(registry.sorted_index, vec![1, 2, 0]);
+        assert_eq!(registry.derived_mismatch(), None);
+
+        let ids: Vec<u64> = registry.entries.iter().map(|e| e.memory_id).collect();
+        assert_eq!(ids, vec![11, 12, 13]);
+        assert!(registry.entries[1].is_encrypted());
+        assert!(!registry.entries[0].is_encrypted());
+        assert_eq!(registry.entries[2].timestamp, 300);
+        assert_eq!(registry.entries[2].last_modified, 300);
+    }
+
+    #[test]
+    fn migration_drops_v0_padding() {
+        let mut entry = v0_entry(1, 100, 0, 1, false);
+        entry[V0_ENTRY_SIZE - 3..].copy_from_slice(&[0xAA; 3]);
+        let mut data = v0_account(&[entry], MemoryRegistry::space_for(1));
+        migrate_v0(&mut data).unwrap();
+
+        let registry = MemoryRegistry::try_deserialize(&mut &data[..]).unwrap();
+        let expected = MemoryEntry {
+            content_hash: [1; 32],
+            timestamp: 100,
+            importance_tier: 1,
+            memory_id: 1,
+            last_modified: 100,
+            ..Default::default()
+        };
+        let serialized = registry.entries[0].try_to_vec().unwrap();
+        assert_eq!(serialized, expected.try_to_vec().unwrap());
+        // The new stride is exactly the serialized entry: nothing to pad.
+        assert_eq!(serialized.len(), MemoryRegistry::ENTRY_SIZE);
+    }
+
+    #[test]
+    fn migrates_an_empty_v0_registry() {
+        let mut data = v0_account(&[], MemoryRegistry::space_for(0));
+        assert_eq!(migrate_v0(&mut data).unwrap(), 0);
+        let registry = MemoryRegistry::try_deserialize(&mut &data[..]).unwrap();
+        assert!(registry.entries.is_empty() && registry.sorted_index.is_empty());
+    }
+
+    #[test]
+    fn refuses_an_account_too_small_for_the_new_layout() {
+        let entries = [v0_entry(1, 100, 0, 1, false), v0_entry(2, 100, 0, 2, false)];
+        let mut data = v0_account(&entries, MemoryRegistry::space_for(1));
+        assert!(migrate_v0(&mut data).is_err());
+    }
+
+    #[test]
+    fn refuses_an_already_versioned_registry() {
+        let mut data = Vec::new();
+        MemoryRegistry::new(Pubkey::default(), 255, 0)
+            .try_serialize(&mut data)
+            .unwrap();
+        assert_eq!(
+            stored_schema_version(&data),
+            Some(MemoryRegistry::SCHEMA_VERSION)
+        );
+        assert!(migrate_v0(&mut data).is_err());
+    }
+
+    #[test]
+    fn rejects_foreign_accounts() {
+        assert_eq!(stored_schema_version(&[0; 64]), None);
+        assert_eq!(stored_schema_version(MemoryRegistry::DISCRIMINATOR), None);
+    }
+}
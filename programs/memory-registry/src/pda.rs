@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+/// First seed of every registry PDA. The accounts structs derive the
+/// registry address from this constant, so it and `registry_pda` cannot
+/// drift apart.
+pub const REGISTRY_SEED: &[u8] = b"memory-registry";
+
+/// Registry PDA and bump for `authority`: seeds `[REGISTRY_SEED, authority]`.
+/// Works off-chain too — depend on this crate with the `client` feature.
+pub fn registry_pda(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REGISTRY_SEED, authority.as_ref()], &crate::ID)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::{NeedsMigration, ReadRegistry, ReadRegistryBumps};
+    use crate::state::MemoryRegistry;
+    use std::collections::BTreeSet;
+
+    fn registry_data(authority: Pubkey, bump: u8) -> Vec<u8> {
+        let mut data = Vec::new();
+        MemoryRegistry::new(authority, bump, 0)
+            .try_serialize(&mut data)
+            .unwrap();
+        data
+    }
+
+    #[test]
+    fn seeds_are_the_published_layout() {
+        let authority = Pubkey::new_unique();
+        let (address, bump) = registry_pda(&authority);
+        let expected =
+            Pubkey::find_program_address(&[b"memory-registry", authority.as_ref()], &crate::ID);
+        assert_eq!((address, bump), expected);
+        assert_ne!(registry_pda(&Pubkey::new_unique()).0, address);
+    }
+
+    #[test]
+    fn accounts_structs_derive_the_same_address() {
+        let authority = Pubkey::new_unique();
+        let (address, bump) = registry_pda(&authority);
+        let mut data = registry_data(authority, bump);
+        let (mut registry_lamports, mut authority_lamports) = (1, 1);
+        let (owner, system) = (crate::ID, Pubkey::default());
+        let infos = [
+            AccountInfo::new(
+                &address, false, false, &mut registry_lamports, &mut data, &owner, false, 0,
+            ),
+            AccountInfo::new(
+                &authority, false, false, &mut authority_lamports, &mut [], &system, false, 0,
+            ),
+        ];
+
+        // `bump = registry.bump` re-derives from the stored bump...
+        let mut bumps = ReadRegistryBumps {};
+        let accounts = ReadRegistry::try_accounts(
+            &crate::ID,
+            &mut &infos[..],
+            &[],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        );
+        assert!(accounts.is_ok());
+
+        // ...and a bare `bump` searches for the canonical one.
+        let mut bumps = Default::default();
+        let accounts = NeedsMigration::try_accounts(
+            &crate::ID,
+            &mut &infos[..],
+            &[],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        );
+        assert!(accounts.is_ok());
+        assert_eq!(bumps.registry, bump);
+    }
+
+    #[test]
+    fn accounts_structs_reject_another_authoritys_registry() {
+        let (authority, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (address, bump) = registry_pda(&other);
+        let mut data = registry_data(authority, bump);
+        let (mut registry_lamports, mut authority_lamports) = (1, 1);
+        let (owner, system) = (crate::ID, Pubkey::default());
+        let infos = [
+            AccountInfo::new(
+                &address, false, false, &mut registry_lamports, &mut data, &owner, false, 0,
+            ),
+            AccountInfo::new(
+                &authority, false, false, &mut authority_lamports, &mut [], &system, false, 0,
+            ),
+        ];
+        let mut bumps = Default::default();
+        let accounts = NeedsMigration::try_accounts(
+            &crate::ID,
+            &mut &infos[..],
+            &[],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        );
+        assert!(accounts.is_err());
+    }
+}
@@ -0,0 +1,261 @@
+use anchor_lang::prelude::*;
+use crate::clock::unix_now;
+use crate::errors::RegistryError;
+use crate::migration::stored_schema_version;
+use crate::state::{MemoryEntry, MemoryRegistry};
+
+/// Byte offset of `expires_at`: discriminator + every header field before it
+/// (see `MemoryRegistry::BASE_SIZE`).
+const EXPIRES_AT_OFFSET: usize = 8 + 32 + 8 + 1 + 4 + 16 + 16 + 12 + 1 + 24 + 32 + 1 + 8 + 4;
+
+/// Byte offset of the `entries` Vec length prefix. The header is
+/// fixed-size, so this is `BASE_SIZE` minus both Vec prefixes.
+const ENTRIES_LEN_OFFSET: usize = MemoryRegistry::BASE_SIZE - 8;
+
+/// Byte offset of the first entry.
+const ENTRIES_OFFSET: usize = ENTRIES_LEN_OFFSET + 4;
+
+/// Offset of `timestamp` within an entry: content_hash.
+const ENTRY_TIMESTAMP_OFFSET: usize = 32;
+
+/// Offset of `importance_tier` within an entry: content_hash + timestamp
+/// + memory_type.
+const ENTRY_TIER_OFFSET: usize = 32 + 8 + 1;
+
+/// Offset of `flags` within an entry: content_hash + timestamp + memory_type
+/// + importance_tier + memory_id.
+const ENTRY_FLAGS_OFFSET: usize = 32 + 8 + 1 + 1 + 8;
+
+/// Read-only view over a registry account's raw bytes, for hot paths that
+/// only need a few fields and can't afford to deserialize every entry.
+/// Offsets follow the Borsh layout of `MemoryRegistry`; keep them in sync
+/// with the struct.
+pub struct RawRegistry<'a> {
+    data: &'a [u8],
+    entry_count: usize,
+}
+
+impl<'a> RawRegistry<'a> {
+    /// Wrap `data`, checking the discriminator, the schema version, and that
+    /// the declared entries and `sorted_index` fit inside it.
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let version =
+            stored_schema_version(data).ok_or(ErrorCode::AccountDiscriminatorMismatch)?;
+        require!(
+            version == MemoryRegistry::SCHEMA_VERSION,
+            RegistryError::UnsupportedSchemaVersion
+        );
+        require!(
+            data.len() >= MemoryRegistry::BASE_SIZE,
+            ErrorCode::AccountDidNotDeserialize
+        );
+        let entry_count = read_u32(data, ENTRIES_LEN_OFFSET) as usize;
+        let needed = entry_count
+            .checked_mul(MemoryRegistry::SLOT_SIZE)
+            .and_then(|n| n.checked_add(MemoryRegistry::BASE_SIZE));
+        require!(
+            needed.is_some_and(|n| n <= data.len()),
+            ErrorCode::AccountDidNotDeserialize
+        );
+        Ok(Self { data, entry_count })
+    }
+
+    pub fn authority(&self) -> Pubkey {
+        Pubkey::new_from_array(self.data[8..40].try_into().unwrap())
+    }
+
+    pub fn expires_at(&self) -> i64 {
+        i64::from_le_bytes(
+            self.data[EXPIRES_AT_OFFSET..EXPIRES_AT_OFFSET + 8]
+                .try_into()
+                .unwrap(),
+        )
+    }
+
+    /// `MemoryRegistry::require_live`, from the raw `expires_at`.
+    pub fn require_live(&self) -> Result<()> {
+        let expires_at = self.expires_at();
+        require!(
+            expires_at == 0 || unix_now()? < expires_at,
+            RegistryError::RegistryExpired
+        );
+        Ok(())
+    }
+
+    pub fn entry_count(&self) -> usize {
+        self.entry_count
+    }
+
+    fn entry_offset(&self, index: usize) -> usize {
+        ENTRIES_OFFSET + index * MemoryRegistry::ENTRY_SIZE
+    }
+
+    pub fn content_hash(&self, index: usize) -> &[u8] {
+        let offset = self.entry_offset(index);
+        &self.data[offset..offset + 32]
+    }
+
+    pub fn timestamp(&self, index: usize) -> i64 {
+        let offset = self.entry_offset(index) + ENTRY_TIMESTAMP_OFFSET;
+        i64::from_le_bytes(self.data[offset..offset + 8].try_into().unwrap())
+    }
+
+    pub fn importance_tier(&self, index: usize) -> u8 {
+        self.data[self.entry_offset(index) + ENTRY_TIER_OFFSET]
+    }
+
+    pub fn flags(&self, index: usize) -> u16 {
+        let offset = self.entry_offset(index) + ENTRY_FLAGS_OFFSET;
+        u16::from_le_bytes(self.data[offset..offset + 2].try_into().unwrap())
+    }
+
+    /// `MemoryEntry::is_live` for the entry at `index`.
+    pub fn is_live(&self, index: usize) -> bool {
+        self.flags(index) & MemoryEntry::HIDDEN_FLAGS == 0
+    }
+
+    /// Deserialize just the entry at `index`.
+    pub fn entry(&self, index: usize) -> Result<MemoryEntry> {
+        let offset = self.entry_offset(index);
+        Ok(MemoryEntry::deserialize(
+            &mut &self.data[offset..offset + MemoryRegistry::ENTRY_SIZE],
+        )?)
+    }
+
+    /// Entry index at position `pos` of `sorted_index`, i.e. the `pos`-th
+    /// entry in hash order.
+    pub fn sorted_slot(&self, pos: usize) -> Result<usize> {
+        let index_offset = self.entry_offset(self.entry_count) + 4;
+        let slot = read_u32(self.data, index_offset + pos * 4) as usize;
+        require!(slot < self.entry_count, ErrorCode::AccountDidNotDeserialize);
+        Ok(slot)
+    }
+
+    /// Whether `content_hash` is registered and active, as `find_active`: a
+    /// binary search over the raw `sorted_index`, comparing hash bytes in
+    /// place. Pending reservations carry the zero hash, so it is never
+    /// reported as registered.
+    pub fn contains(&self, content_hash: &[u8; 32]) -> bool {
+        if *content_hash == [0; 32] {
+            return false;
+        }
+        // `sorted_index` follows the entries and its own length prefix.
+        let index_offset = ENTRIES_OFFSET + self.entry_count * MemoryRegistry::ENTRY_SIZE + 4;
+        let (mut low, mut high) = (0, self.entry_count);
+        while low < high {
+            let mid = (low + high) / 2;
+            let slot = read_u32(self.data, index_offset + mid * 4) as usize;
+            if slot >= self.entry_count {
+                return false;
+            }
+            match self.content_hash(slot).cmp(&content_hash[..]) {
+                std::cmp::Ordering::Less => low = mid + 1,
+                std::cmp::Ordering::Greater => high = mid,
+                std::cmp::Ordering::Equal => {
+                    return self.is_live(slot);
+                }
+            }
+        }
+        false
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::entrypoint::HEAP_LENGTH;
+    use crate::migration::SCHEMA_VERSION_OFFSET;
+
+    fn hash(i: u32) -> [u8; 32] {
+        let mut hash = [0xEE; 32];
+        // Spread the bytes so insertion order differs from hash order.
+        hash[..4].copy_from_slice(&i.wrapping_mul(2_654_435_761).to_be_bytes());
+        hash
+    }
+
+    fn registry_with(n: u32) -> MemoryRegistry {
+        let mut registry = MemoryRegistry::new(Pubkey::new_unique(), 254, 1_800_000_000);
+        for i in 0..n {
+            let mut entry = MemoryEntry {
+                content_hash: hash(i),
+                timestamp: 1_000 + i as i64,
+                importance_tier: (i % 3) as u8,
+                memory_id: i as u64,
+                ..Default::default()
+            };
+            entry.set_flag(MemoryEntry::FLAG_QUARANTINED, i % 7 == 6);
+            registry.push_entry(entry);
+        }
+        registry
+    }
+
+    fn bytes(registry: &MemoryRegistry) -> Vec<u8> {
+        let mut data = Vec::new();
+        registry.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn offsets_match_the_borsh_layout() {
+        let registry = registry_with(20);
+        let data = bytes(&registry);
+        let raw = RawRegistry::parse(&data).unwrap();
+
+        assert_eq!(raw.authority(), registry.authority);
+        assert_eq!(raw.expires_at(), registry.expires_at);
+        assert_eq!(raw.entry_count(), 20);
+        for (i, entry) in registry.entries.iter().enumerate() {
+            assert_eq!(raw.content_hash(i), &entry.content_hash[..]);
+            assert_eq!(raw.timestamp(i), entry.timestamp);
+            assert_eq!(raw.importance_tier(i), entry.importance_tier);
+            assert_eq!(raw.flags(i), entry.flags);
+            assert_eq!(raw.entry(i).unwrap().memory_id, entry.memory_id);
+        }
+        for (pos, &slot) in registry.sorted_index.iter().enumerate() {
+            assert_eq!(raw.sorted_slot(pos).unwrap(), slot as usize);
+        }
+    }
+
+    #[test]
+    fn contains_matches_find_active_on_2000_entries() {
+        let registry = registry_with(2_000);
+        // Far more than `verify_memory` can deserialize onto the heap; the
+        // raw path only ever reads the header and ~11 index probes.
+        assert!(MemoryRegistry::space_for(2_000) > HEAP_LENGTH);
+
+        let data = bytes(&registry);
+        let raw = RawRegistry::parse(&data).unwrap();
+        for i in 0..2_000 {
+            assert_eq!(
+                raw.contains(&hash(i)),
+                registry.find_active(&hash(i)).is_some(),
+                "entry {i}"
+            );
+        }
+        assert!(!raw.contains(&hash(2_000)));
+        assert!(!raw.contains(&[0; 32]));
+        assert!(!raw.contains(&hash(6)), "quarantined");
+    }
+
+    #[test]
+    fn parse_rejects_malformed_accounts() {
+        let data = bytes(&registry_with(3));
+        assert!(RawRegistry::parse(&data[..data.len() - 1]).is_err());
+        assert!(RawRegistry::parse(&data[..MemoryRegistry::BASE_SIZE - 1]).is_err());
+
+        let mut foreign = data.clone();
+        foreign[0] ^= 1;
+        assert!(RawRegistry::parse(&foreign).is_err());
+
+        // An original-layout account holds its entries length where
+        // `schema_version` now sits.
+        let mut original = data;
+        original[SCHEMA_VERSION_OFFSET..SCHEMA_VERSION_OFFSET + 4]
+            .copy_from_slice(&3u32.to_le_bytes());
+        assert!(RawRegistry::parse(&original).is_err());
+    }
+}
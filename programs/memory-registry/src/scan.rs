@@ -0,0 +1,35 @@
+use anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
+
+/// Compute units a bounded scan leaves untouched for its own epilogue
+/// (account write-back, realloc, return data, logging).
+pub const CU_RESERVE: u64 = 25_000;
+
+/// Work budget for resumable maintenance scans. Each `tick` spends one unit
+/// of the caller's `max_work` and checks the remaining compute budget, so a
+/// scan stops — and hands back a resumption cursor — either when it has done
+/// the work it was asked for or when it is about to run out of CU, whichever
+/// comes first. This adapts to whatever compute-budget limit the client set.
+pub struct WorkBudget {
+    remaining: u32,
+    exhausted: bool,
+}
+
+impl WorkBudget {
+    pub fn new(max_work: u32) -> Self {
+        Self {
+            remaining: max_work,
+            exhausted: false,
+        }
+    }
+
+    /// Claim one unit of work. Returns false (and stays false) once the
+    /// budget or the transaction's compute is spent.
+    pub fn tick(&mut self) -> bool {
+        if self.exhausted || self.remaining == 0 || sol_remaining_compute_units() < CU_RESERVE {
+            self.exhausted = true;
+            return false;
+        }
+        self.remaining -= 1;
+        true
+    }
+}
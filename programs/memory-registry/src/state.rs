@@ -1,36 +1,901 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::entrypoint::HEAP_LENGTH;
+use anchor_lang::solana_program::program::MAX_RETURN_DATA;
+use crate::clock::unix_now;
+use crate::error_context::fail_with_context;
+use crate::errors::RegistryError;
 
 /// On-chain memory registry PDA — stores content hashes + metadata per wallet.
-/// Seeds: ["memory-registry", authority]
+/// Seeds: ["memory-registry", authority] — see `pda::registry_pda`
 #[account]
 pub struct MemoryRegistry {
     /// Wallet that owns this registry.
     pub authority: Pubkey,
-    /// Number of memory entries stored.
+    /// Number of memory entries stored (including pending reservations).
     pub memory_count: u64,
     /// PDA bump seed.
     pub bump: u8,
+    /// Account layout version, `VERSION_TAG | SCHEMA_VERSION` for this
+    /// program. Pre-versioning registries kept their entries length in this
+    /// word; see `migration::stored_schema_version`.
+    pub schema_version: u32,
+    /// Stored entry count per memory type (indexed by `memory_type`).
+    pub type_counts: [u32; 4],
+    /// Optional per-type entry caps (indexed by `memory_type`); 0 = unlimited.
+    pub type_caps: [u32; 4],
+    /// Stored entry count per importance tier (indexed by `importance_tier`).
+    pub tier_counts: [u32; 3],
+    /// What `register_memory` does with an already-registered hash: one of
+    /// `DUPLICATE_REJECT` (default), `DUPLICATE_IGNORE`, `DUPLICATE_UPDATE`.
+    pub duplicate_policy: u8,
+    /// Default TTL in seconds per importance tier (indexed by
+    /// `importance_tier`); 0 = never expires. Applied when a registration
+    /// doesn't pass an explicit expiry.
+    pub tier_ttls: [i64; 3],
+    /// Allowed content schema ids; only the first `schema_count` are live.
+    pub schemas: [u16; 16],
+    /// Number of live ids in `schemas`.
+    pub schema_count: u8,
+    /// Mutation counter, bumped on every state change (entries or settings).
+    /// Feeds `sync_hash`.
+    pub nonce: u64,
+    /// Epoch new entries are tagged with. Every epoch below it has been
+    /// sealed by `seal_epoch`, so its entries are immutable.
+    pub current_epoch: u32,
+    /// Unix timestamp after which the whole registry is expired; 0 = never.
+    /// Past it, writes and verifications fail with `RegistryExpired` and
+    /// anyone may close the account via `reap_registry`. Until then the
+    /// authority can move it with `renew_registry`.
+    pub expires_at: i64,
+    /// Second key that must co-sign destructive instructions (`seal_epoch`,
+    /// `replace_all`, `commit_replace`, `clear_entries`, `prune_and_compact`,
+    /// `sweep`, `dedupe`, `set_co_authority`, and limit changes that evict
+    /// entries); `Pubkey::default()` = none. A plain `Pubkey` rather than
+    /// `Option` so the header stays fixed-size for raw parsers.
+    pub co_authority: Pubkey,
+    /// Outstanding single-use write grants; each slot lets its key register
+    /// one memory via `register_memory_with_grant`. `Pubkey::default()` marks
+    /// a free slot; a key may hold several slots.
+    pub write_grants: [Pubkey; 4],
+    /// Episodic ring size; 0 = disabled. When set, a new episodic (type 0)
+    /// entry that would exceed it evicts the oldest episodic entries instead
+    /// of growing or failing the cap check.
+    pub ring_capacity: u32,
+    /// Importance decay period in seconds; 0 = disabled. Each full period an
+    /// entry goes without being rewritten lowers its tier by one (see
+    /// `decay`).
+    pub decay_after_seconds: i64,
+    /// Stored entries with `FLAG_ENCRYPTED` set, quarantined ones included.
+    /// Maintained alongside `type_counts`.
+    pub encrypted_count: u32,
+    /// Most stored entries the registry keeps; 0 = unlimited. A registration
+    /// that would exceed it first evicts the oldest entries by `timestamp`.
+    pub max_retained: u32,
+    /// When set, every write must be encrypted; plaintext registrations,
+    /// finalizations and replacements fail with `EncryptionRequired`.
+    pub require_encryption: bool,
+    /// Key whose Ed25519 signature `register_memory_timestamped` accepts for
+    /// an entry's timestamp; `Pubkey::default()` = none (that path refuses).
+    pub time_oracle: Pubkey,
+    /// Circuit breaker: more than this many registrations within
+    /// `burst_window` seconds sets `frozen`; 0 = disabled.
+    pub burst_threshold: u32,
+    /// Length of the circuit breaker's counting window, in seconds.
+    pub burst_window: i64,
+    /// Start of the current counting window.
+    pub burst_window_start: i64,
+    /// Registrations counted in the current window.
+    pub burst_count: u32,
+    /// Set when the circuit breaker trips. While set, registrations,
+    /// reservations and finalizations fail with `RegistryFrozen` until the
+    /// authority calls `unfreeze_registry`.
+    pub frozen: bool,
     /// Variable-length list of memory entries.
+    ///
+    /// Ordering invariant: entries are kept in insertion order. New entries
+    /// are only ever appended, and removals go through `remove_entry`, which
+    /// shifts later entries down (O(n)) rather than swapping in the tail.
+    /// Clients parsing the raw account may rely on this order.
     pub entries: Vec<MemoryEntry>,
+    /// Indices into `entries`, ordered by the referenced entry's
+    /// `content_hash`. Maintained on every insert/remove so lookups can
+    /// binary-search without reordering `entries`. Kept after `entries` so
+    /// the entry offsets stay fixed for raw-account parsers.
+    pub sorted_index: Vec<u32>,
 }
 
 impl MemoryRegistry {
-    /// Base size: discriminator(8) + authority(32) + memory_count(8) + bump(1) + vec_prefix(4)
-    pub const BASE_SIZE: usize = 8 + 32 + 8 + 1 + 4;
+    /// Base size: discriminator(8) + authority(32) + memory_count(8) + bump(1)
+    /// + schema_version(4) + type_counts(16) + type_caps(16) + tier_counts(12)
+    /// + duplicate_policy(1) + tier_ttls(24) + schemas(32) + schema_count(1)
+    /// + nonce(8) + current_epoch(4) + expires_at(8) + co_authority(32)
+    /// + write_grants(128) + ring_capacity(4)
+    /// + decay_after_seconds(8) + encrypted_count(4) + max_retained(4)
+    /// + require_encryption(1) + time_oracle(32) + burst_threshold(4)
+    /// + burst_window(8) + burst_window_start(8) + burst_count(4) + frozen(1)
+    /// + entries vec_prefix(4) + sorted_index vec_prefix(4)
+    ///
+    /// The TypeScript client mirrors this layout in `REGISTRY_LAYOUT`
+    /// (packages/shared/src/core/solana-client.ts); change both together.
+    pub const BASE_SIZE: usize =
+        8 + 32 + 8 + 1 + 4 + 16 + 16 + 12 + 1 + 24 + 32 + 1 + 8 + 4 + 8 + 32 + 128 + 4 + 8
+            + 4 + 4 + 4 + 4 + 1 + 32 + 4 + 8 + 8 + 4 + 1;
 
-    /// Size per entry (aligned): hash(32) + timestamp(8) + memory_type(1) + importance_tier(1)
-    /// + memory_id(8) + encrypted(1) = 51, padded to 56 for alignment
-    pub const ENTRY_SIZE: usize = 56;
+    /// Size per entry (packed): hash(32) + timestamp(8) + memory_type(1) +
+    /// importance_tier(1) + memory_id(8) + flags(2) + revision(2) + expires_at(8) +
+    /// schema_id(2) + preview_hash(32) + epoch(4) + confidence(1) + decay_steps(1) +
+    /// encryption_nonce(12) + app_data(32) + content_kind(1) + cluster_id(2) +
+    /// last_modified(8) + derived_from(32) + derived_count(1) = 190 (no padding)
+    pub const ENTRY_SIZE: usize = 190;
+
+    /// Layout version written by this program. Bump it with every change to
+    /// the account layout, and teach `migrate_registry` the previous one.
+    pub const SCHEMA_VERSION: u32 = 1;
+
+    /// Set on every stored `schema_version`. Version 0 registries hold their
+    /// entries length in that word instead, and no real entry count reaches
+    /// this bit, so the two can be told apart from the raw bytes alone.
+    pub const VERSION_TAG: u32 = 1 << 31;
+
+    /// Capacity of `write_grants`.
+    pub const MAX_WRITE_GRANTS: usize = 4;
+
+    /// Capacity of the `schemas` allow-list.
+    pub const MAX_SCHEMAS: usize = 16;
+
+    /// Size per `sorted_index` element (u32).
+    pub const INDEX_ENTRY_SIZE: usize = 4;
+
+    /// Account bytes consumed per stored memory: the entry plus its index
+    /// slot. The index adds ~4% to per-entry rent.
+    pub const SLOT_SIZE: usize = Self::ENTRY_SIZE + Self::INDEX_ENTRY_SIZE;
+
+    /// Duplicate registration fails with `DuplicateHash`.
+    pub const DUPLICATE_REJECT: u8 = 0;
+    /// Duplicate registration is a silent no-op: nothing is stored,
+    /// `MemoryRegistered` reports `written = false`, and no write grant is
+    /// consumed. The `realloc` constraint has already grown the account by
+    /// then; the payer's rent stays in it as headroom for the next write.
+    pub const DUPLICATE_IGNORE: u8 = 1;
+    /// Duplicate registration overwrites the existing entry's metadata.
+    pub const DUPLICATE_UPDATE: u8 = 2;
+
+    /// `derived_mismatch` field codes: which derived field disagreed.
+    pub const DERIVED_MEMORY_COUNT: u64 = 0;
+    pub const DERIVED_TYPE_COUNTS: u64 = 1;
+    pub const DERIVED_TIER_COUNTS: u64 = 2;
+    pub const DERIVED_SORTED_INDEX: u64 = 3;
+    pub const DERIVED_ENCRYPTED_COUNT: u64 = 4;
+
+    /// Most entries a paginated read can return: whatever fits in the
+    /// 1024-byte return-data buffer after the 4-byte Vec prefix.
+    pub const MAX_PAGE_ENTRIES: usize = (MAX_RETURN_DATA - 4) / Self::ENTRY_SIZE;
 
     /// Initial capacity (entries).
     pub const INITIAL_CAPACITY: usize = 50;
 
+    /// Heap kept free for everything but the registry's own Vecs while it is
+    /// loaded: instruction arguments, events, logs and return data.
+    pub const HEAP_RESERVE: usize = 2 * 1024;
+
+    /// Most entries a registry may hold and still be loaded as an
+    /// `Account<MemoryRegistry>` and take one more push, within the 32 KiB
+    /// heap (see `load_heap_bytes`). Every path that adds entries or grows
+    /// the account stops here with `RegistryFull`; past it the registry
+    /// could only be read through `RawRegistry`.
+    pub const MAX_REGISTRY_ENTRIES: usize =
+        Self::max_entries_for(std::mem::size_of::<MemoryEntry>());
+
     /// Entries added per realloc.
     pub const REALLOC_INCREMENT: usize = 10;
 
+    /// Heap bytes spent deserializing a registry of `len` entries and then
+    /// pushing one more. Borsh starts each Vec at `4096 / size_of` elements
+    /// (`borsh::de::hint::cautious`) and doubles it as it fills, and the SBF
+    /// bump allocator never frees an outgrown buffer, so every capacity on
+    /// the way counts. Pending reservations are entries too.
+    pub const fn load_heap_bytes(len: usize) -> usize {
+        Self::load_heap_bytes_for(len, std::mem::size_of::<MemoryEntry>())
+    }
+
+    /// `load_heap_bytes` for an in-memory entry of `entry_bytes`.
+    const fn load_heap_bytes_for(len: usize, entry_bytes: usize) -> usize {
+        Self::vec_heap_bytes(len, entry_bytes) + Self::vec_heap_bytes(len, Self::INDEX_ENTRY_SIZE)
+    }
+
+    /// `MAX_REGISTRY_ENTRIES` for an in-memory entry of `entry_bytes`. Every
+    /// fixed field an entry carries lowers it, used or not: 32 more bytes per
+    /// entry cost roughly 20 entries of ceiling.
+    pub const fn max_entries_for(entry_bytes: usize) -> usize {
+        let mut n = 0;
+        while Self::load_heap_bytes_for(n + 1, entry_bytes) + Self::HEAP_RESERVE <= HEAP_LENGTH {
+            n += 1;
+        }
+        n
+    }
+
+    /// `load_heap_bytes` for one Vec of `elem`-byte elements.
+    const fn vec_heap_bytes(len: usize, elem: usize) -> usize {
+        let hint = 4096 / elem;
+        let mut cap = if len < hint { len } else { hint };
+        if cap == 0 {
+            cap = 1;
+        }
+        let mut total = cap * elem;
+        // Decoding doubles until `len` fits; the push doubles once more if
+        // it filled exactly.
+        while cap < len + 1 {
+            cap *= 2;
+            total += cap * elem;
+        }
+        total
+    }
+
+    /// Fail with `RegistryFull` unless one more entry keeps the registry
+    /// within `MAX_REGISTRY_ENTRIES`. Call before any `push_entry`.
+    pub fn require_room(&self) -> Result<()> {
+        require!(
+            self.entries.len() < Self::MAX_REGISTRY_ENTRIES,
+            RegistryError::RegistryFull
+        );
+        Ok(())
+    }
+
+    /// A freshly initialized registry: no entries, every setting at its
+    /// default, and the current `schema_version`.
+    pub fn new(authority: Pubkey, bump: u8, expires_at: i64) -> Self {
+        Self {
+            authority,
+            memory_count: 0,
+            bump,
+            schema_version: Self::VERSION_TAG | Self::SCHEMA_VERSION,
+            type_counts: [0; 4],
+            type_caps: [0; 4],
+            tier_counts: [0; 3],
+            duplicate_policy: Self::DUPLICATE_REJECT,
+            tier_ttls: [0; 3],
+            schemas: [0; Self::MAX_SCHEMAS],
+            schema_count: 0,
+            nonce: 0,
+            current_epoch: 0,
+            expires_at,
+            co_authority: Pubkey::default(),
+            write_grants: [Pubkey::default(); Self::MAX_WRITE_GRANTS],
+            ring_capacity: 0,
+            decay_after_seconds: 0,
+            encrypted_count: 0,
+            max_retained: 0,
+            require_encryption: false,
+            time_oracle: Pubkey::default(),
+            burst_threshold: 0,
+            burst_window: 0,
+            burst_window_start: 0,
+            burst_count: 0,
+            frozen: false,
+            entries: Vec::new(),
+            sorted_index: Vec::new(),
+        }
+    }
+
     /// Space for N entries.
     pub fn space_for(n: usize) -> usize {
-        Self::BASE_SIZE + n * Self::ENTRY_SIZE
+        Self::BASE_SIZE + n * Self::SLOT_SIZE
+    }
+
+    /// Rent-exempt lamports for a registry sized for `n` entries. Pure given
+    /// `rent`, so clients can call it with `Rent::default()` or fetched params.
+    pub fn rent_for(rent: &Rent, n: usize) -> u64 {
+        rent.minimum_balance(Self::space_for(n))
+    }
+
+    /// Account length to realloc to before appending one entry, given the
+    /// account's current `data_len`. Keeps the current length while it still
+    /// fits `len + 1` entries; otherwise grows by `REALLOC_INCREMENT` slots.
+    /// Decided from the real allocation — the deserialized Vec's capacity
+    /// says nothing about it.
+    pub fn grow_target(&self, data_len: usize) -> usize {
+        let needed = Self::space_for(self.entries.len() + 1);
+        if data_len >= needed {
+            data_len
+        } else {
+            needed + Self::REALLOC_INCREMENT * Self::SLOT_SIZE
+        }
+    }
+
+    /// Expiry implied by the tier's default TTL for an entry written at
+    /// `timestamp` (0 = never). `importance_tier` must already be validated.
+    pub fn default_expiry(&self, importance_tier: u8, timestamp: i64) -> i64 {
+        match self.tier_ttls[importance_tier as usize] {
+            0 => 0,
+            ttl => timestamp.saturating_add(ttl),
+        }
+    }
+
+    /// One page of live (`MemoryEntry::is_live`) entries matching
+    /// `filter`, in insertion order: skips the first `start` matches and returns up to
+    /// `limit`. Fails with `PageLimitExceeded` above `MAX_PAGE_ENTRIES`, and
+    /// as `require_live` once the registry has expired.
+    pub fn page_where(
+        &self,
+        start: u32,
+        limit: u32,
+        filter: impl Fn(&MemoryEntry) -> bool,
+    ) -> Result<Vec<MemoryEntry>> {
+        self.require_live()?;
+        require!(
+            limit as usize <= Self::MAX_PAGE_ENTRIES,
+            RegistryError::PageLimitExceeded
+        );
+
+        Ok(self
+            .entries
+            .iter()
+            .filter(|e| e.is_live() && filter(e))
+            .skip(start as usize)
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    /// Allowed content schema ids.
+    pub fn schema_ids(&self) -> &[u16] {
+        &self.schemas[..self.schema_count as usize]
+    }
+
+    /// Whether `schema_id` is on the allow-list.
+    pub fn has_schema(&self, schema_id: u16) -> bool {
+        self.schema_ids().contains(&schema_id)
+    }
+
+    /// Compact sync token: sha256(memory_count || nonce). Changes on every
+    /// mutation; also serves as the optimistic-concurrency digest for
+    /// `replace_all` / `commit_replace`.
+    pub fn sync_token(&self) -> [u8; 32] {
+        hashv(&[&self.memory_count.to_le_bytes(), &self.nonce.to_le_bytes()]).to_bytes()
+    }
+
+    /// Fail with `StateChanged` unless `expected` is the current
+    /// `sync_token`: the caller must have seen the state it is replacing.
+    pub fn require_sync_token(&self, expected: &[u8; 32]) -> Result<()> {
+        require!(self.sync_token() == *expected, RegistryError::StateChanged);
+        Ok(())
+    }
+
+    /// Whether the registry's own lifetime has run out at `now`.
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    /// Fail with `RegistryExpired` once the registry's lifetime is over.
+    /// Every write handler calls this first, and so does every read that
+    /// answers whether or how particular entries exist: verifies, lookups
+    /// and listings.
+    ///
+    /// Exempt are the calls the authority needs to diagnose an expired
+    /// registry or wind it down: aggregate statistics and counters,
+    /// `sync_hash`, `get_registry_info`, storage and rent metrics,
+    /// `self_check`, `repair_counters`, `list_schemas`, and `export_canonical`
+    /// (so it can be backed up before `reap_registry`). `renew_registry` is
+    /// exempt by design.
+    pub fn require_live(&self) -> Result<()> {
+        let now = unix_now()?;
+        require!(!self.is_expired(now), RegistryError::RegistryExpired);
+        Ok(())
+    }
+
+    /// Fail with `CoAuthorityRequired` unless `co_signer` is the configured
+    /// co-authority. Always passes when none is set. Destructive handlers
+    /// call this with their optional `co_authority` signer.
+    pub fn require_co_signed(&self, co_signer: Option<&Signer<'_>>) -> Result<()> {
+        if self.co_authority == Pubkey::default() {
+            return Ok(());
+        }
+        require!(
+            co_signer.is_some_and(|signer| signer.key() == self.co_authority),
+            RegistryError::CoAuthorityRequired
+        );
+        Ok(())
+    }
+
+    /// Fail with `RegistryFrozen` while the circuit breaker is tripped.
+    pub fn require_unfrozen(&self) -> Result<()> {
+        require!(!self.frozen, RegistryError::RegistryFrozen);
+        Ok(())
+    }
+
+    /// Count one registration against the circuit breaker at `now`, starting
+    /// a new window once the current one has elapsed. Returns true if this
+    /// registration tripped the breaker and froze the registry. No-op while
+    /// the breaker is disabled.
+    pub fn record_registration(&mut self, now: i64) -> bool {
+        if self.burst_threshold == 0 {
+            return false;
+        }
+        if now.saturating_sub(self.burst_window_start) >= self.burst_window {
+            self.burst_window_start = now;
+            self.burst_count = 0;
+        }
+        self.burst_count = self.burst_count.saturating_add(1);
+        if self.burst_count > self.burst_threshold && !self.frozen {
+            self.frozen = true;
+            return true;
+        }
+        false
+    }
+
+    /// Evict the oldest episodic entries until one more fits the ring. No-op
+    /// with the ring disabled. Sealed and immutable entries are never evicted;
+    /// fails with `RingFull` if only those are left.
+    pub fn make_ring_room(&mut self) -> Result<()> {
+        let capacity = self.ring_capacity;
+        if capacity == 0 {
+            return Ok(());
+        }
+        while self.type_counts[0] >= capacity {
+            let oldest = self
+                .eviction_victim(|e| e.memory_type == 0)
+                .ok_or(RegistryError::RingFull)?;
+            self.remove_entry(oldest);
+        }
+        Ok(())
+    }
+
+    /// The entry automatic eviction removes next among stored, mutable entries
+    /// matching `candidate`: the oldest by `timestamp`, ties broken by the
+    /// smaller `content_hash`. The order is total and depends only on entry
+    /// contents, so the pick is reproducible whatever the insertion history.
+    /// Every eviction path (ring, retention) selects through here.
+    pub fn eviction_victim(&self, candidate: impl Fn(&MemoryEntry) -> bool) -> Option<usize> {
+        (0..self.entries.len())
+            .filter(|&i| {
+                let entry = &self.entries[i];
+                !entry.is_pending() && candidate(entry) && self.require_mutable(i).is_ok()
+            })
+            .min_by_key(|&i| (self.entries[i].timestamp, self.entries[i].content_hash))
+    }
+
+    /// Number of stored (non-pending) entries, quarantined ones included:
+    /// the figure retention and the per-type caps limit. Recall statistics
+    /// count `MemoryEntry::is_live` entries instead.
+    pub fn stored_count(&self) -> u32 {
+        self.type_counts.iter().sum()
+    }
+
+    /// Evict stored entries in `eviction_victim` order until at most `keep`
+    /// remain. Sealed and immutable entries are never evicted, so this may
+    /// stop short; returns how many were removed.
+    pub fn evict_oldest(&mut self, keep: u32) -> u32 {
+        let mut evicted = 0;
+        while self.stored_count() > keep {
+            match self.eviction_victim(|_| true) {
+                Some(index) => {
+                    self.remove_entry(index);
+                    evicted += 1;
+                }
+                None => break,
+            }
+        }
+        evicted
+    }
+
+    /// Whether `grantee` holds at least one write-grant slot.
+    pub fn has_write_grant(&self, grantee: &Pubkey) -> bool {
+        self.write_grants.contains(grantee)
+    }
+
+    /// Clear one write-grant slot held by `grantee`. Returns false if it
+    /// holds none.
+    pub fn take_write_grant(&mut self, grantee: &Pubkey) -> bool {
+        match self.write_grants.iter_mut().find(|slot| *slot == grantee) {
+            Some(slot) => {
+                *slot = Pubkey::default();
+                self.bump_nonce();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stamp the entry at `index` as modified at `now` and record the state
+    /// change. Handlers call this after changing an entry in place. Restarts
+    /// the entry's decay clock.
+    pub fn touch_entry(&mut self, index: usize, now: i64) {
+        let entry = &mut self.entries[index];
+        entry.last_modified = now;
+        entry.decay_steps = 0;
+        self.bump_nonce();
+    }
+
+    /// Record a state change. The entry helpers below call this themselves;
+    /// handlers that mutate anything else must call it directly.
+    pub fn bump_nonce(&mut self) {
+        self.nonce = self.nonce.wrapping_add(1);
+    }
+
+    /// Binary-search `sorted_index` for `content_hash`: `Ok(pos)` if present,
+    /// `Err(pos)` with the insertion position otherwise.
+    fn search_index(&self, content_hash: &[u8; 32]) -> std::result::Result<usize, usize> {
+        self.sorted_index
+            .binary_search_by(|&i| self.entries[i as usize].content_hash.cmp(content_hash))
+    }
+
+    /// Index into `entries` of the entry with `content_hash`, if any (O(log n)).
+    /// Pending reservations (zero hash) are never returned.
+    pub fn find_entry(&self, content_hash: &[u8; 32]) -> Option<usize> {
+        self.search_index(content_hash)
+            .ok()
+            .map(|pos| self.sorted_index[pos] as usize)
+            .filter(|&index| !self.entries[index].is_pending())
+    }
+
+    /// Like `find_entry`, but also hides quarantined entries. Recall reads
+    /// (verifies, lookups, listings) use this; write paths keep `find_entry`
+    /// so a quarantined hash still counts for duplicate checks.
+    pub fn find_active(&self, content_hash: &[u8; 32]) -> Option<usize> {
+        self.find_entry(content_hash)
+            .filter(|&index| self.entries[index].is_live())
+    }
+
+    /// Whether the entry at `index` belongs to a sealed epoch. Pending
+    /// reservations are never sealed; they join the current epoch on finalize.
+    pub fn is_sealed(&self, index: usize) -> bool {
+        let entry = &self.entries[index];
+        !entry.is_pending() && entry.epoch < self.current_epoch
+    }
+
+    /// Fail unless the entry at `index` may be changed or removed: not in a
+    /// sealed epoch (`EpochSealed`), not write-once (`EntryImmutable`) and
+    /// not held for review (`EntryQuarantined`).
+    pub fn require_mutable(&self, index: usize) -> Result<()> {
+        require!(!self.is_sealed(index), RegistryError::EpochSealed);
+        require!(!self.entries[index].is_immutable(), RegistryError::EntryImmutable);
+        require!(
+            !self.entries[index].is_quarantined(),
+            RegistryError::EntryQuarantined
+        );
+        Ok(())
+    }
+
+    /// Index into `entries` of the pending reservation for `memory_id`, if any.
+    pub fn find_reservation(&self, memory_id: u64) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|e| e.is_pending() && e.memory_id == memory_id)
+    }
+
+    /// Append an entry, keeping `sorted_index` and the derived counters in
+    /// sync. Callers are responsible for the duplicate check. Every insertion
+    /// path must go through here.
+    pub fn push_entry(&mut self, mut entry: MemoryEntry) {
+        entry.epoch = self.current_epoch;
+        let pos = match self.search_index(&entry.content_hash) {
+            Ok(pos) | Err(pos) => pos,
+        };
+        self.sorted_index.insert(pos, self.entries.len() as u32);
+
+        // Pending reservations have no type yet; they're counted on finalize.
+        if !entry.is_pending() {
+            self.count_entry(&entry);
+        }
+        self.entries.push(entry);
+        self.memory_count = self.entries.len() as u64;
+        self.bump_nonce();
+    }
+
+    /// Add a stored entry to the per-type, per-tier and encrypted counters.
+    pub(crate) fn count_entry(&mut self, entry: &MemoryEntry) {
+        let (t, i) = (entry.memory_type as usize, entry.importance_tier as usize);
+        self.type_counts[t] = self.type_counts[t].saturating_add(1);
+        self.tier_counts[i] = self.tier_counts[i].saturating_add(1);
+        if entry.is_encrypted() {
+            self.encrypted_count = self.encrypted_count.saturating_add(1);
+        }
+    }
+
+    /// Remove a stored entry from the per-type, per-tier and encrypted counters.
+    fn uncount_entry(&mut self, entry: &MemoryEntry) {
+        let (t, i) = (entry.memory_type as usize, entry.importance_tier as usize);
+        self.type_counts[t] = self.type_counts[t].saturating_sub(1);
+        self.tier_counts[i] = self.tier_counts[i].saturating_sub(1);
+        if entry.is_encrypted() {
+            self.encrypted_count = self.encrypted_count.saturating_sub(1);
+        }
+    }
+
+    /// Set or clear `FLAG_ENCRYPTED` on the stored entry at `index`, keeping
+    /// `encrypted_count` in sync.
+    pub fn set_encrypted(&mut self, index: usize, encrypted: bool) {
+        let entry = &mut self.entries[index];
+        if entry.is_encrypted() != encrypted {
+            entry.set_flag(MemoryEntry::FLAG_ENCRYPTED, encrypted);
+            self.encrypted_count = if encrypted {
+                self.encrypted_count.saturating_add(1)
+            } else {
+                self.encrypted_count.saturating_sub(1)
+            };
+        }
+        self.bump_nonce();
+    }
+
+    /// Change the importance tier of the entry at `index`, moving it between
+    /// the per-tier counters.
+    pub fn set_importance_tier(&mut self, index: usize, importance_tier: u8) {
+        let old_tier = self.entries[index].importance_tier as usize;
+        self.tier_counts[old_tier] = self.tier_counts[old_tier].saturating_sub(1);
+        self.tier_counts[importance_tier as usize] =
+            self.tier_counts[importance_tier as usize].saturating_add(1);
+        self.entries[index].importance_tier = importance_tier;
+        self.bump_nonce();
+    }
+
+    /// Change the memory type of the entry at `index`, moving it between the
+    /// per-type counters. Callers are responsible for the cap check.
+    pub fn set_memory_type(&mut self, index: usize, memory_type: u8) {
+        let old_type = self.entries[index].memory_type as usize;
+        self.type_counts[old_type] = self.type_counts[old_type].saturating_sub(1);
+        self.type_counts[memory_type as usize] =
+            self.type_counts[memory_type as usize].saturating_add(1);
+        self.entries[index].memory_type = memory_type;
+        self.bump_nonce();
+    }
+
+    /// Replace the content hash of the entry at `index` in place, moving it to
+    /// its new position in `sorted_index`. Callers are responsible for the
+    /// duplicate check.
+    pub fn set_content_hash(&mut self, index: usize, content_hash: [u8; 32]) {
+        let slot = index as u32;
+        self.sorted_index.retain(|&i| i != slot);
+        self.entries[index].content_hash = content_hash;
+        let pos = match self.search_index(&content_hash) {
+            Ok(pos) | Err(pos) => pos,
+        };
+        self.sorted_index.insert(pos, slot);
+        self.bump_nonce();
+    }
+
+    /// Turn the pending reservation at `index` into a live entry. Callers are
+    /// responsible for the duplicate and cap checks.
+    pub fn finalize_entry(
+        &mut self,
+        index: usize,
+        content_hash: [u8; 32],
+        memory_type: u8,
+        importance_tier: u8,
+        encrypted: bool,
+        timestamp: i64,
+    ) {
+        let expires_at = self.default_expiry(importance_tier, timestamp);
+        self.set_content_hash(index, content_hash);
+
+        let entry = &mut self.entries[index];
+        entry.memory_type = memory_type;
+        entry.importance_tier = importance_tier;
+        entry.set_flag(MemoryEntry::FLAG_ENCRYPTED, encrypted);
+        entry.timestamp = timestamp;
+        entry.last_modified = timestamp;
+        entry.decay_steps = 0;
+        entry.expires_at = expires_at;
+        entry.epoch = self.current_epoch;
+        entry.set_flag(MemoryEntry::FLAG_PENDING, false);
+
+        let entry = self.entries[index].clone();
+        self.count_entry(&entry);
+    }
+
+    /// Replace the whole entry set, rebuilding `sorted_index` and every
+    /// derived counter from scratch. Each entry is validated as a fresh
+    /// registration would be; duplicates and pending placeholders are
+    /// rejected. Fails as `require_mutable` if any current entry is sealed or
+    /// immutable, and tags every new entry with the current epoch and time.
+    ///
+    /// Supplied timestamps are never trusted: an entry whose hash is already
+    /// registered keeps its stored timestamp, and any other is stamped now,
+    /// so a replace or restore cannot backdate a `verify_before` proof.
+    pub fn replace_entries(&mut self, entries: Vec<MemoryEntry>) -> Result<()> {
+        self.replace_entries_at(entries, unix_now()?)
+    }
+
+    /// `replace_entries` with the current time supplied.
+    pub fn replace_entries_at(&mut self, mut entries: Vec<MemoryEntry>, now: i64) -> Result<()> {
+        require!(
+            entries.len() <= Self::MAX_REGISTRY_ENTRIES,
+            RegistryError::RegistryFull
+        );
+        for index in 0..self.entries.len() {
+            self.require_mutable(index)?;
+        }
+        for entry in entries.iter_mut() {
+            entry.epoch = self.current_epoch;
+            entry.last_modified = now;
+            entry.decay_steps = 0;
+            entry.timestamp = match self.find_entry(&entry.content_hash) {
+                Some(index) => self.entries[index].timestamp,
+                None => now,
+            };
+            require!(entry.memory_type <= 3, RegistryError::InvalidMemoryType);
+            require!(entry.importance_tier <= 2, RegistryError::InvalidImportanceTier);
+            require!(
+                entry.confidence <= MemoryEntry::MAX_CONFIDENCE,
+                RegistryError::InvalidConfidence
+            );
+            require!(
+                !entry.is_pending() && entry.content_hash != [0; 32],
+                RegistryError::InvalidContentHash
+            );
+            require!(
+                entry.schema_id == 0 || self.has_schema(entry.schema_id),
+                RegistryError::UnknownSchema
+            );
+            require!(
+                entry.is_encrypted() || entry.encryption_nonce == [0; 12],
+                RegistryError::NonceWithoutEncryption
+            );
+            require!(
+                entry.content_kind <= MemoryEntry::MAX_CONTENT_KIND,
+                RegistryError::InvalidContentKind
+            );
+            require!(
+                entry.is_encrypted() || !self.require_encryption,
+                RegistryError::EncryptionRequired
+            );
+            require!(
+                entry.derived_count as usize <= MemoryEntry::MAX_DERIVED_FROM,
+                RegistryError::TooManySources
+            );
+        }
+
+        self.entries = entries;
+        self.rebuild_derived();
+
+        let duplicate = self.sorted_index.windows(2).any(|pair| {
+            self.entries[pair[0] as usize].content_hash
+                == self.entries[pair[1] as usize].content_hash
+        });
+        require!(!duplicate, RegistryError::DuplicateHash);
+        self.check_replaced_limits()?;
+
+        self.bump_nonce();
+        Ok(())
+    }
+
+    /// The registry limits `check_insert_policy` applies one write at a time,
+    /// checked against a whole replaced set. A replace evicts nothing, so an
+    /// over-limit set fails instead: per-type caps, the episodic ring, the
+    /// retention limit, and provenance sources that must be in the set.
+    fn check_replaced_limits(&self) -> Result<()> {
+        for (memory_type, (&count, &cap)) in
+            self.type_counts.iter().zip(self.type_caps.iter()).enumerate()
+        {
+            if cap != 0 && count > cap {
+                return Err(fail_with_context(
+                    RegistryError::TypeCapExceeded,
+                    memory_type as u64,
+                    count as u64,
+                    cap as u64,
+                ));
+            }
+        }
+        require!(
+            self.ring_capacity == 0 || self.type_counts[0] <= self.ring_capacity,
+            RegistryError::RingFull
+        );
+        require!(
+            self.max_retained == 0 || self.stored_count() <= self.max_retained,
+            RegistryError::RetentionFull
+        );
+        let registered = |id: &u64| {
+            self.entries
+                .iter()
+                .any(|entry| !entry.is_pending() && entry.memory_id == *id)
+        };
+        require!(
+            self.entries
+                .iter()
+                .all(|entry| entry.provenance().iter().all(registered)),
+            RegistryError::SourceMissing
+        );
+        Ok(())
+    }
+
+    /// Recompute `sorted_index`, `type_counts`, `tier_counts`,
+    /// `encrypted_count` and `memory_count` from `entries`.
+    pub fn rebuild_derived(&mut self) {
+        let entries = &self.entries;
+        let mut sorted_index: Vec<u32> = (0..entries.len() as u32).collect();
+        sorted_index.sort_by(|&a, &b| {
+            entries[a as usize]
+                .content_hash
+                .cmp(&entries[b as usize].content_hash)
+        });
+        self.sorted_index = sorted_index;
+
+        self.type_counts = [0; 4];
+        self.tier_counts = [0; 3];
+        self.encrypted_count = 0;
+        for index in 0..self.entries.len() {
+            let entry = self.entries[index].clone();
+            if !entry.is_pending() {
+                self.count_entry(&entry);
+            }
+        }
+        self.memory_count = self.entries.len() as u64;
+    }
+
+    /// First derived field that disagrees with `entries`, as
+    /// `(field, stored, expected)`. `field` is one of the `DERIVED_*` codes;
+    /// for array fields the values are those of the first differing element.
+    /// `sorted_index` reports the first out-of-place position as `stored`
+    /// and the entry count as `expected`.
+    pub fn derived_mismatch(&self) -> Option<(u64, u64, u64)> {
+        let n = self.entries.len();
+        if self.memory_count != n as u64 {
+            return Some((Self::DERIVED_MEMORY_COUNT, self.memory_count, n as u64));
+        }
+
+        let mut type_counts = [0u32; 4];
+        let mut tier_counts = [0u32; 3];
+        let mut encrypted_count = [0u32; 1];
+        for entry in self.entries.iter().filter(|e| !e.is_pending()) {
+            type_counts[entry.memory_type as usize] += 1;
+            tier_counts[entry.importance_tier as usize] += 1;
+            encrypted_count[0] += entry.is_encrypted() as u32;
+        }
+        for (field, stored, expected) in [
+            (Self::DERIVED_TYPE_COUNTS, &self.type_counts[..], &type_counts[..]),
+            (Self::DERIVED_TIER_COUNTS, &self.tier_counts[..], &tier_counts[..]),
+            (
+                Self::DERIVED_ENCRYPTED_COUNT,
+                std::slice::from_ref(&self.encrypted_count),
+                &encrypted_count[..],
+            ),
+        ] {
+            if let Some((s, e)) = stored.iter().zip(expected).find(|(s, e)| s != e) {
+                return Some((field, *s as u64, *e as u64));
+            }
+        }
+
+        // `sorted_index` must be a permutation of 0..n ordered by hash.
+        let mut seen = vec![false; n];
+        let mut previous: Option<&[u8; 32]> = None;
+        for (pos, &i) in self.sorted_index.iter().enumerate() {
+            let i = i as usize;
+            let valid = i < n
+                && !seen[i]
+                && previous.is_none_or(|p| *p <= self.entries[i].content_hash);
+            if !valid {
+                return Some((Self::DERIVED_SORTED_INDEX, pos as u64, n as u64));
+            }
+            seen[i] = true;
+            previous = Some(&self.entries[i].content_hash);
+        }
+        if self.sorted_index.len() != n {
+            return Some((Self::DERIVED_SORTED_INDEX, self.sorted_index.len() as u64, n as u64));
+        }
+
+        None
+    }
+
+    /// Remove the entry at `index`, preserving the insertion order of the
+    /// remaining entries, and keep `sorted_index` and the derived counters in
+    /// sync. Every removal path must go through here — never `swap_remove` on
+    /// `entries`.
+    pub fn remove_entry(&mut self, index: usize) -> MemoryEntry {
+        let entry = self.entries.remove(index);
+        let removed = index as u32;
+        self.sorted_index.retain(|&i| i != removed);
+        for i in self.sorted_index.iter_mut() {
+            if *i > removed {
+                *i -= 1;
+            }
+        }
+
+        if !entry.is_pending() {
+            self.uncount_entry(&entry);
+        }
+        self.memory_count = self.entries.len() as u64;
+        self.bump_nonce();
+        entry
     }
 }
 
@@ -47,10 +912,138 @@ pub struct MemoryEntry {
     pub importance_tier: u8,
     /// Supabase memory ID for cross-reference.
     pub memory_id: u64,
-    /// Whether the memory content is encrypted at rest.
-    pub encrypted: bool,
-    /// Padding for 8-byte alignment (3 bytes).
-    pub _padding: [u8; 3],
+    /// Boolean attributes as `FLAG_*` bits (encrypted, pending, immutable,
+    /// public, quarantined); read and write them through the helpers below.
+    pub flags: u16,
+    /// Number of times the content hash has been replaced via `rehash_memory`.
+    pub revision: u16,
+    /// Unix timestamp after which the memory is expired; 0 = never.
+    pub expires_at: i64,
+    /// Content schema id (see `MemoryRegistry::schemas`); 0 = unstructured.
+    pub schema_id: u16,
+    /// Hash of a redacted preview snippet; all zeros = no preview. Never
+    /// consulted for dedup, which keys on `content_hash` alone. Inline in
+    /// every entry, so it costs heap ceiling even when unset (see
+    /// `MemoryRegistry::max_entries_for`).
+    pub preview_hash: [u8; 32],
+    /// Epoch the entry was registered in (see `MemoryRegistry::current_epoch`).
+    pub epoch: u32,
+    /// How sure the agent is of the memory, 0-100. Independent of importance.
+    pub confidence: u8,
+    /// Decay periods already applied since `last_modified`; reset whenever
+    /// that is stamped. Decay itself never stamps it.
+    pub decay_steps: u8,
+    /// AES-GCM nonce/IV the content was encrypted with; all zeros = none.
+    /// Only allowed with `FLAG_ENCRYPTED`.
+    pub encryption_nonce: [u8; 12],
+    /// Application-defined bytes, opaque to the program and never consulted
+    /// for dedup; all zeros = none. Inline like `preview_hash`: the two
+    /// together take `MAX_REGISTRY_ENTRIES` from 127 down to 83.
+    pub app_data: [u8; 32],
+    /// What the content is, for rendering: one of the `CONTENT_*` kinds.
+    pub content_kind: u8,
+    /// Client-assigned embedding cluster, for semantic grouping; 0 =
+    /// unassigned. Informational only, never consulted for dedup.
+    pub cluster_id: u16,
+    /// Unix timestamp of the last write to this entry: set on insert and on
+    /// every in-place change. Drives `changed_since` delta sync.
+    pub last_modified: i64,
+    /// `memory_id`s of the entries this memory was synthesized from; only
+    /// the first `derived_count` are set. Checked to exist at registration
+    /// (see `register_memory_derived`); a source removed later is not
+    /// unlinked.
+    pub derived_from: [u64; 4],
+    /// Number of live ids in `derived_from`.
+    pub derived_count: u8,
+}
+
+impl MemoryEntry {
+    /// Upper bound of `confidence`.
+    pub const MAX_CONFIDENCE: u8 = 100;
+
+    /// Capacity of `derived_from`.
+    pub const MAX_DERIVED_FROM: usize = 4;
+
+    /// `content_kind` values.
+    pub const CONTENT_TEXT: u8 = 0;
+    pub const CONTENT_AUDIO: u8 = 1;
+    pub const CONTENT_IMAGE: u8 = 2;
+    pub const CONTENT_VIDEO: u8 = 3;
+    pub const CONTENT_STRUCTURED: u8 = 4;
+    /// Highest valid `content_kind`.
+    pub const MAX_CONTENT_KIND: u8 = Self::CONTENT_STRUCTURED;
+
+    /// The memory content is encrypted at rest.
+    pub const FLAG_ENCRYPTED: u16 = 1 << 0;
+    /// Slot reserved via `reserve_memory` but not yet finalized. Pending
+    /// entries carry a zero hash, have no type, and are invisible to lookups.
+    pub const FLAG_PENDING: u16 = 1 << 1;
+    /// Write-once entry: never updated, rehashed, retiered or removed.
+    pub const FLAG_IMMUTABLE: u16 = 1 << 2;
+    /// Published for third parties; visible to `verify_public`.
+    pub const FLAG_PUBLIC: u16 = 1 << 3;
+    /// Held for review via `quarantine_memory`: hidden from recall reads and
+    /// exempt from every update and removal until `release_memory`.
+    pub const FLAG_QUARANTINED: u16 = 1 << 4;
+    /// Flags that keep an entry out of every recall read and statistic.
+    pub const HIDDEN_FLAGS: u16 = Self::FLAG_PENDING | Self::FLAG_QUARANTINED;
+
+    pub fn has_flag(&self, flag: u16) -> bool {
+        self.flags & flag != 0
+    }
+
+    pub fn set_flag(&mut self, flag: u16, on: bool) {
+        if on {
+            self.flags |= flag;
+        } else {
+            self.flags &= !flag;
+        }
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.has_flag(Self::FLAG_ENCRYPTED)
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.has_flag(Self::FLAG_PENDING)
+    }
+
+    pub fn is_immutable(&self) -> bool {
+        self.has_flag(Self::FLAG_IMMUTABLE)
+    }
+
+    pub fn is_public(&self) -> bool {
+        self.has_flag(Self::FLAG_PUBLIC)
+    }
+
+    pub fn is_quarantined(&self) -> bool {
+        self.has_flag(Self::FLAG_QUARANTINED)
+    }
+
+    /// Visible to recall: neither a pending reservation nor quarantined.
+    /// Listings, lookups and every statistic count exactly these entries.
+    pub fn is_live(&self) -> bool {
+        self.flags & Self::HIDDEN_FLAGS == 0
+    }
+
+    /// The live prefix of `derived_from`.
+    pub fn provenance(&self) -> &[u64] {
+        &self.derived_from[..(self.derived_count as usize).min(Self::MAX_DERIVED_FROM)]
+    }
+
+    /// Whether the entry's expiry has passed at `now`. Never-expiring
+    /// entries (`expires_at == 0`), including pending reservations, are not.
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && self.expires_at <= now
+    }
+
+    /// Whether the entry was registered at or before `as_of_ts`, the
+    /// `verify_before` proof. `timestamp` is the last registration time, so a
+    /// `DUPLICATE_UPDATE` overwrite moves it forward and can fail a proof
+    /// that held before.
+    pub fn registered_by(&self, as_of_ts: i64) -> bool {
+        self.timestamp <= as_of_ts
+    }
 }
 
 /// A shared memory pool — any wallet can write after paying the write fee.
@@ -145,3 +1138,389 @@ pub struct CitationRecord {
     /// future `kind: u8` discriminator or epoch tag without migration).
     pub _reserved: [u8; 8],
 }
+
+/// Staging buffer for a chunked `replace_all`: entries are appended across
+/// several transactions via `stage_replace_entries`, then swapped into the
+/// registry atomically by `commit_replace`.
+/// Seeds: `["replace-staging", registry]`
+#[account]
+pub struct ReplaceStaging {
+    /// Registry this staging buffer will replace.
+    pub registry: Pubkey,
+    /// PDA bump.
+    pub bump: u8,
+    /// Entries staged so far.
+    pub entries: Vec<MemoryEntry>,
+}
+
+impl ReplaceStaging {
+    /// Base size: discriminator(8) + registry(32) + bump(1) + vec_prefix(4)
+    pub const BASE_SIZE: usize = 8 + 32 + 1 + 4;
+
+    /// Space for N staged entries.
+    pub fn space_for(n: usize) -> usize {
+        Self::BASE_SIZE + n * MemoryRegistry::ENTRY_SIZE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(hash: u8, timestamp: i64) -> MemoryEntry {
+        MemoryEntry {
+            content_hash: [hash; 32],
+            timestamp,
+            ..Default::default()
+        }
+    }
+
+    fn registry_of(hashes: &[u8]) -> MemoryRegistry {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        for &h in hashes {
+            registry.push_entry(entry(h, h as i64));
+        }
+        registry
+    }
+
+    fn hash_order(registry: &MemoryRegistry) -> Vec<u8> {
+        registry
+            .sorted_index
+            .iter()
+            .map(|&i| registry.entries[i as usize].content_hash[0])
+            .collect()
+    }
+
+    #[test]
+    fn sorted_index_tracks_push_remove_and_rehash() {
+        let mut registry = registry_of(&[5, 2, 9, 1, 7]);
+        assert_eq!(hash_order(&registry), vec![1, 2, 5, 7, 9]);
+        assert_eq!(registry.find_entry(&[9; 32]), Some(2));
+
+        // Removing shifts later slots down but keeps insertion order.
+        let removed = registry.remove_entry(1);
+        assert_eq!(removed.content_hash, [2; 32]);
+        assert_eq!(hash_order(&registry), vec![1, 5, 7, 9]);
+        assert_eq!(registry.find_entry(&[9; 32]), Some(1));
+        assert_eq!(registry.find_entry(&[2; 32]), None);
+
+        registry.set_content_hash(0, [8; 32]);
+        assert_eq!(hash_order(&registry), vec![1, 7, 8, 9]);
+        assert_eq!(registry.derived_mismatch(), None);
+
+        let incremental = registry.sorted_index.clone();
+        registry.rebuild_derived();
+        assert_eq!(registry.sorted_index, incremental);
+    }
+
+    #[test]
+    fn flags_set_and_clear_independently() {
+        let mut entry = MemoryEntry::default();
+        entry.set_flag(MemoryEntry::FLAG_ENCRYPTED, true);
+        entry.set_flag(MemoryEntry::FLAG_QUARANTINED, true);
+        assert!(entry.is_encrypted() && entry.is_quarantined());
+        assert!(!entry.is_pending() && !entry.is_immutable() && !entry.is_public());
+
+        entry.set_flag(MemoryEntry::FLAG_ENCRYPTED, false);
+        assert!(!entry.is_encrypted() && entry.is_quarantined());
+        assert_eq!(entry.flags, MemoryEntry::FLAG_QUARANTINED);
+    }
+
+    #[test]
+    fn is_live_hides_pending_and_quarantined_only() {
+        let mut entry = MemoryEntry::default();
+        assert!(entry.is_live());
+        entry.flags =
+            MemoryEntry::FLAG_ENCRYPTED | MemoryEntry::FLAG_IMMUTABLE | MemoryEntry::FLAG_PUBLIC;
+        assert!(entry.is_live());
+        for flag in [MemoryEntry::FLAG_PENDING, MemoryEntry::FLAG_QUARANTINED] {
+            entry.flags = flag;
+            assert!(!entry.is_live());
+        }
+    }
+
+    #[test]
+    fn quarantined_entries_are_stored_but_not_live() {
+        let mut registry = registry_of(&[1, 2, 3]);
+        registry.entries[1].set_flag(MemoryEntry::FLAG_QUARANTINED, true);
+        assert_eq!(registry.entries.iter().filter(|e| e.is_live()).count(), 2);
+        assert!(registry.find_active(&[2; 32]).is_none());
+        assert!(registry.find_entry(&[2; 32]).is_some(), "writes still see it");
+        assert_eq!(registry.stored_count(), 3);
+    }
+
+    #[test]
+    fn write_grants_are_checked_then_taken_one_at_a_time() {
+        let mut registry = registry_of(&[]);
+        let grantee = Pubkey::new_unique();
+        registry.write_grants[0] = grantee;
+        registry.write_grants[2] = grantee;
+        assert!(registry.has_write_grant(&grantee));
+        assert!(!registry.has_write_grant(&Pubkey::new_unique()));
+
+        let nonce = registry.nonce;
+        assert!(registry.take_write_grant(&grantee));
+        assert!(registry.has_write_grant(&grantee), "one slot left");
+        assert!(registry.take_write_grant(&grantee));
+        assert!(!registry.has_write_grant(&grantee));
+        assert!(!registry.take_write_grant(&grantee));
+        assert_eq!(registry.nonce, nonce + 2);
+    }
+
+    /// Counts every byte allocated on the current thread and never subtracts
+    /// frees, as the SBF bump allocator behaves.
+    struct BumpCounting;
+
+    thread_local! {
+        static ALLOCATED: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    unsafe impl std::alloc::GlobalAlloc for BumpCounting {
+        unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+            let _ = ALLOCATED.try_with(|a| a.set(a.get() + layout.size()));
+            std::alloc::System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+            std::alloc::System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: BumpCounting = BumpCounting;
+
+    /// Heap a deserialize of `n` entries plus one `push_entry` really uses.
+    fn measured_load_heap(n: usize) -> usize {
+        let mut registry = registry_of(&[]);
+        registry.entries = (0..n)
+            .map(|i| {
+                let mut e = entry(0, i as i64);
+                e.content_hash[..8].copy_from_slice(&(i as u64 + 1).to_be_bytes());
+                e
+            })
+            .collect();
+        registry.rebuild_derived();
+        let mut data = Vec::new();
+        registry.try_serialize(&mut data).unwrap();
+
+        let before = ALLOCATED.with(|a| a.get());
+        let mut loaded = MemoryRegistry::try_deserialize(&mut &data[..]).unwrap();
+        loaded.push_entry(entry(0xFF, 0));
+        let used = ALLOCATED.with(|a| a.get()) - before;
+        drop(loaded);
+        used
+    }
+
+    #[test]
+    fn load_heap_model_matches_real_allocations() {
+        assert_eq!(std::mem::size_of::<MemoryEntry>(), 192);
+        for n in [21, 22, 41, 42, 43, 60, MemoryRegistry::MAX_REGISTRY_ENTRIES] {
+            assert_eq!(measured_load_heap(n), MemoryRegistry::load_heap_bytes(n), "n = {n}");
+        }
+        for n in [0, 1, 5, 20] {
+            assert!(measured_load_heap(n) <= HEAP_LENGTH - MemoryRegistry::HEAP_RESERVE);
+        }
+    }
+
+    #[test]
+    fn max_registry_entries_is_the_last_loadable_size() {
+        let max = MemoryRegistry::MAX_REGISTRY_ENTRIES;
+        assert_eq!(max, 83);
+        assert!(max >= MemoryRegistry::INITIAL_CAPACITY);
+        let budget = HEAP_LENGTH - MemoryRegistry::HEAP_RESERVE;
+        assert!(MemoryRegistry::load_heap_bytes(max) <= budget);
+        assert!(MemoryRegistry::load_heap_bytes(max + 1) > budget);
+    }
+
+    #[test]
+    fn inline_hash_fields_are_sized_against_the_heap_ceiling() {
+        let entry = std::mem::size_of::<MemoryEntry>();
+        // `preview_hash` and `app_data` are 32 bytes each, set or not.
+        assert_eq!(MemoryRegistry::max_entries_for(entry), 83);
+        assert_eq!(MemoryRegistry::max_entries_for(entry - 32), 99);
+        assert_eq!(MemoryRegistry::max_entries_for(entry - 64), 127);
+    }
+
+    #[test]
+    fn require_room_stops_at_the_ceiling() {
+        let mut registry = registry_of(&[]);
+        for i in 0..MemoryRegistry::MAX_REGISTRY_ENTRIES - 1 {
+            registry.entries.push(entry(i as u8, 0));
+        }
+        assert!(registry.require_room().is_ok());
+        registry.entries.push(entry(0xFF, 0));
+        assert!(registry.require_room().is_err());
+
+        let too_many = vec![MemoryEntry::default(); MemoryRegistry::MAX_REGISTRY_ENTRIES + 1];
+        let err = registry.replace_entries_at(too_many, 0).unwrap_err();
+        assert_eq!(err, RegistryError::RegistryFull.into());
+    }
+
+    #[test]
+    fn grow_target_keeps_room_and_grows_by_the_increment() {
+        let registry = registry_of(&[1, 2, 3]);
+        let roomy = MemoryRegistry::space_for(10);
+        assert_eq!(registry.grow_target(roomy), roomy);
+        let exact = MemoryRegistry::space_for(4);
+        assert_eq!(registry.grow_target(exact), exact);
+        assert_eq!(
+            registry.grow_target(MemoryRegistry::space_for(3)),
+            MemoryRegistry::space_for(4 + MemoryRegistry::REALLOC_INCREMENT)
+        );
+    }
+
+    #[test]
+    fn eviction_victim_is_oldest_then_smallest_hash() {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        registry.push_entry(entry(9, 100));
+        registry.push_entry(entry(4, 100));
+        registry.push_entry(entry(1, 300));
+        let mut immutable = entry(2, 50);
+        immutable.set_flag(MemoryEntry::FLAG_IMMUTABLE, true);
+        registry.push_entry(immutable);
+        let mut pending = entry(0, 0);
+        pending.set_flag(MemoryEntry::FLAG_PENDING, true);
+        registry.push_entry(pending);
+
+        // Same timestamp: the smaller hash goes first, whatever was inserted
+        // first. Immutable and pending entries are never picked.
+        assert_eq!(registry.eviction_victim(|_| true), Some(1));
+        assert_eq!(registry.eviction_victim(|e| e.content_hash[0] != 4), Some(0));
+        assert_eq!(registry.eviction_victim(|e| e.content_hash[0] == 2), None);
+
+        assert_eq!(registry.evict_oldest(2), 2);
+        assert_eq!(registry.stored_count(), 2);
+        assert_eq!(registry.evict_oldest(0), 1);
+        assert_eq!(registry.stored_count(), 1, "the immutable entry stays");
+    }
+
+    #[test]
+    fn derived_mismatch_reports_the_first_bad_field() {
+        let mut registry = registry_of(&[3, 1, 2]);
+        assert_eq!(registry.derived_mismatch(), None);
+
+        registry.memory_count = 7;
+        assert_eq!(
+            registry.derived_mismatch(),
+            Some((MemoryRegistry::DERIVED_MEMORY_COUNT, 7, 3))
+        );
+        registry.memory_count = 3;
+
+        registry.tier_counts[0] = 1;
+        assert_eq!(
+            registry.derived_mismatch(),
+            Some((MemoryRegistry::DERIVED_TIER_COUNTS, 1, 3))
+        );
+        registry.tier_counts[0] = 3;
+
+        registry.encrypted_count = 2;
+        assert_eq!(
+            registry.derived_mismatch(),
+            Some((MemoryRegistry::DERIVED_ENCRYPTED_COUNT, 2, 0))
+        );
+        registry.encrypted_count = 0;
+
+        registry.sorted_index.swap(0, 2);
+        assert_eq!(
+            registry.derived_mismatch(),
+            Some((MemoryRegistry::DERIVED_SORTED_INDEX, 1, 3))
+        );
+        registry.sorted_index.pop();
+        registry.rebuild_derived();
+        assert_eq!(registry.derived_mismatch(), None);
+    }
+
+    #[test]
+    fn rent_for_tracks_space_for() {
+        let rent = Rent::default();
+        assert_eq!(
+            MemoryRegistry::rent_for(&rent, 0),
+            rent.minimum_balance(MemoryRegistry::BASE_SIZE)
+        );
+        let per_slot = MemoryRegistry::rent_for(&rent, 11) - MemoryRegistry::rent_for(&rent, 10);
+        assert_eq!(
+            per_slot,
+            rent.minimum_balance(MemoryRegistry::SLOT_SIZE) - rent.minimum_balance(0)
+        );
+    }
+
+    #[test]
+    fn registered_by_includes_the_registration_second() {
+        let entry = entry(1, 1_000);
+        assert!(entry.registered_by(1_001));
+        assert!(entry.registered_by(1_000));
+        assert!(!entry.registered_by(999));
+    }
+
+    #[test]
+    fn replace_swaps_the_set_and_invalidates_the_digest() {
+        let mut registry = registry_of(&[1, 2]);
+        let digest = registry.sync_token();
+        registry.require_sync_token(&digest).unwrap();
+
+        registry
+            .replace_entries_at(vec![entry(9, 0), entry(3, 0), entry(5, 0)], 100)
+            .unwrap();
+        assert_eq!(hash_order(&registry), vec![3, 5, 9]);
+        assert_eq!(registry.memory_count, 3);
+        assert_eq!(registry.type_counts[0], 3);
+        assert!(registry.find_entry(&[1; 32]).is_none());
+
+        // The digest the caller saw no longer matches.
+        let err = registry.require_sync_token(&digest).unwrap_err();
+        assert_eq!(err, RegistryError::StateChanged.into());
+        registry.require_sync_token(&registry.sync_token()).unwrap();
+    }
+
+    #[test]
+    fn replace_enforces_caps_ring_retention_and_sources() {
+        let three = || vec![entry(1, 0), entry(2, 0), entry(3, 0)];
+        let fails_with = |registry: &mut MemoryRegistry, entries, error: RegistryError| {
+            let err = registry.replace_entries_at(entries, 0).unwrap_err();
+            assert_eq!(err, error.into());
+        };
+
+        let mut registry = registry_of(&[]);
+        registry.type_caps[0] = 2;
+        fails_with(&mut registry, three(), RegistryError::TypeCapExceeded);
+
+        let mut registry = registry_of(&[]);
+        registry.ring_capacity = 2;
+        fails_with(&mut registry, three(), RegistryError::RingFull);
+
+        let mut registry = registry_of(&[]);
+        registry.max_retained = 2;
+        fails_with(&mut registry, three(), RegistryError::RetentionFull);
+
+        let mut cited = entry(4, 0);
+        cited.derived_from[0] = 77;
+        cited.derived_count = 1;
+        let mut registry = registry_of(&[]);
+        fails_with(&mut registry, vec![entry(1, 0), cited.clone()], RegistryError::SourceMissing);
+
+        let mut source = entry(1, 0);
+        source.memory_id = 77;
+        registry.replace_entries_at(vec![source, cited], 0).unwrap();
+
+        let mut registry = registry_of(&[]);
+        registry.type_caps[0] = 3;
+        registry.ring_capacity = 3;
+        registry.max_retained = 3;
+        registry.replace_entries_at(three(), 0).unwrap();
+    }
+
+    #[test]
+    fn replace_keeps_stored_timestamps_and_stamps_new_entries() {
+        let mut registry = MemoryRegistry::new(Pubkey::default(), 255, 0);
+        registry.push_entry(entry(1, 500));
+
+        // Both supplied timestamps try to predate what the registry knows.
+        registry
+            .replace_entries_at(vec![entry(1, 10), entry(2, 20)], 9_000)
+            .unwrap();
+        let by_hash = |h: u8| registry.entries[registry.find_entry(&[h; 32]).unwrap()].clone();
+        assert_eq!(by_hash(1).timestamp, 500);
+        assert_eq!(by_hash(2).timestamp, 9_000);
+        assert_eq!(by_hash(2).last_modified, 9_000);
+    }
+}